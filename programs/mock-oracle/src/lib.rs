@@ -0,0 +1,96 @@
+use anchor_lang::prelude::*;
+
+declare_id!("BuzzngnvLmyRXcZZyLUnyuqt1HtKrUGbr5vVsaV4BVmN");
+
+/// Seed of the single SOL/USD feed account
+pub const SOL_USD_FEED_SEED: &[u8] = b"sol-usd";
+
+/// Address of the SOL/USD feed account
+pub fn sol_usd_feed_address() -> Pubkey {
+    Pubkey::find_program_address(&[SOL_USD_FEED_SEED], &ID).0
+}
+
+/// Stand-in for the Chainlink and Pyth SOL/USD feeds on localnet.
+/// The test authority sets the price, confidence and publish time directly.
+#[program]
+pub mod mock_oracle {
+    use super::*;
+
+    /// Create the feed with the signer as its authority
+    pub fn initialize_feed(ctx: Context<InitializeFeed>, price: i128, conf: u64) -> Result<()> {
+        let feed = &mut ctx.accounts.feed;
+        feed.authority = ctx.accounts.authority.key();
+        feed.price = price;
+        feed.conf = conf;
+        feed.timestamp = Clock::get()?.unix_timestamp;
+        Ok(())
+    }
+
+    /// Publish a new price and confidence interval (8 decimals), stamped with the current
+    /// time unless `timestamp` overrides it to simulate a stale feed
+    pub fn set_price(
+        ctx: Context<SetPrice>,
+        price: i128,
+        conf: u64,
+        timestamp: Option<i64>,
+    ) -> Result<()> {
+        let feed = &mut ctx.accounts.feed;
+        feed.price = price;
+        feed.conf = conf;
+        feed.timestamp = match timestamp {
+            Some(timestamp) => timestamp,
+            None => Clock::get()?.unix_timestamp,
+        };
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct InitializeFeed<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + PriceFeed::LEN,
+        seeds = [SOL_USD_FEED_SEED],
+        bump
+    )]
+    pub feed: Account<'info, PriceFeed>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetPrice<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [SOL_USD_FEED_SEED],
+        bump,
+        has_one = authority
+    )]
+    pub feed: Account<'info, PriceFeed>,
+}
+
+#[account]
+#[derive(Default)]
+pub struct PriceFeed {
+    /// Only signer allowed to move the price
+    pub authority: Pubkey,
+
+    /// SOL/USD price (8 decimals)
+    pub price: i128,
+
+    /// Confidence interval around `price` (8 decimals)
+    pub conf: u64,
+
+    /// Publish time of `price`
+    pub timestamp: i64,
+}
+
+impl PriceFeed {
+    pub const LEN: usize = 32 + 16 + 8 + 8;
+}
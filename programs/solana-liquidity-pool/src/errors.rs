@@ -20,4 +20,70 @@ pub enum VaultError {
     NoLPTokens,
     #[msg("Insufficient reward balance.")]
     InsufficientRewardBalance,
+    #[msg("Lock duration must be between 1 and 52 weeks.")]
+    InvalidLockDuration,
+    #[msg("A lock can only be extended, not shortened.")]
+    LockCannotBeShortened,
+    #[msg("User has no active LP lock.")]
+    NoActiveLock,
+    #[msg("LP tokens are locked.")]
+    LpTokensLocked,
+    #[msg("Amount must be greater than zero.")]
+    ZeroAmount,
+    #[msg("Basis points must not exceed 10000.")]
+    InvalidBasisPoints,
+    #[msg("Reward vesting is not enabled.")]
+    VestingDisabled,
+    #[msg("Nothing has vested yet.")]
+    NothingVested,
+    #[msg("Delegated claims are disabled while instant claims carry a haircut.")]
+    DelegatedClaimHaircut,
+    #[msg("Account is not a valid user state.")]
+    InvalidUserState,
+    #[msg("Invalid merkle proof.")]
+    InvalidMerkleProof,
+    #[msg("Reward already claimed for this index.")]
+    AlreadyClaimed,
+    #[msg("Claim index is out of range.")]
+    InvalidClaimIndex,
+    #[msg("Too many claims for a single distributor.")]
+    TooManyClaims,
+    #[msg("Distributor has expired.")]
+    DistributorExpired,
+    #[msg("Distributor has not expired yet.")]
+    DistributorNotExpired,
+    #[msg("Reward sweep grace period has not passed.")]
+    SweepGracePeriodActive,
+    #[msg("No treasury configured.")]
+    TreasuryNotSet,
+    #[msg("Invalid referral code.")]
+    InvalidReferralCode,
+    #[msg("Users cannot refer themselves.")]
+    SelfReferral,
+    #[msg("Mint uses an unsupported Token-2022 extension.")]
+    UnsupportedMintExtension,
+    #[msg("Swaps are disabled.")]
+    SwapsDisabled,
+    #[msg("Output amount is below the minimum.")]
+    SlippageExceeded,
+    #[msg("Swap would push the pool further outside its composition limits.")]
+    CompositionLimitExceeded,
+    #[msg("Not enough liquidity in the output vault.")]
+    InsufficientLiquidity,
+    #[msg("Too soon since the last snapshot.")]
+    SnapshotTooSoon,
+    #[msg("Invalid price policy.")]
+    InvalidPricePolicy,
+    #[msg("Invalid Pyth price account.")]
+    InvalidPriceFeed,
+    #[msg("Oracle price is stale.")]
+    StalePrice,
+    #[msg("Oracle confidence interval is too wide.")]
+    PriceConfidenceTooWide,
+    #[msg("The same user state was passed more than once.")]
+    DuplicateUserState,
+    #[msg("Referral rewards owed to the referrer must be claimed first.")]
+    ReferralRewardsOutstanding,
+    #[msg("Pending rewards must be claimed first.")]
+    PendingRewardsOutstanding,
 }
@@ -0,0 +1,360 @@
+use anchor_lang::prelude::*;
+
+/// Schema version carried by every versioned event, bumped on layout changes
+pub const EVENT_SCHEMA_VERSION: u8 = 3;
+
+// -----------------------------------------------
+// Reward events
+// -----------------------------------------------
+
+#[event]
+pub struct RewardsClaimed {
+    pub version: u8,
+    pub user: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+    pub total_claimed: u64,
+}
+
+/// Pending rewards moved into the user's vesting schedule
+#[event]
+pub struct RewardsVested {
+    pub version: u8,
+    pub user: Pubkey,
+    pub amount: u64,
+    /// Vesting schedule total after this claim
+    pub vesting_total: u64,
+    pub vesting_end_time: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct LpLocked {
+    pub version: u8,
+    pub user: Pubkey,
+    /// LP added to the lock by this call
+    pub amount: u64,
+    /// Total LP locked after this call
+    pub locked_lp_amount: u64,
+    pub lock_weeks: u64,
+    pub lock_end_time: u64,
+    pub multiplier_bps: u64,
+    pub boosted_balance: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct LpUnlocked {
+    pub version: u8,
+    pub user: Pubkey,
+    pub unlocked_amount: u64,
+    /// LP burned for unlocking before `lock_end_time`
+    pub penalty: u64,
+    pub boosted_balance: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DistributorCreated {
+    pub version: u8,
+    pub campaign_id: u64,
+    pub mint: Pubkey,
+    pub vault: Pubkey,
+    /// Amount that arrived in the vault, net of any transfer fee
+    pub total_amount: u64,
+    pub num_claims: u64,
+    pub expiry_time: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DistributorClawedBack {
+    pub version: u8,
+    pub campaign_id: u64,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MerkleRewardsClaimed {
+    pub version: u8,
+    pub campaign_id: u64,
+    pub user: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+    pub total_claimed: u64,
+}
+
+#[event]
+pub struct RewardsSwept {
+    pub version: u8,
+    pub treasury: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ReferralCodeRegistered {
+    pub version: u8,
+    pub referrer: Pubkey,
+    pub code: [u8; 8],
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ReferralRegistered {
+    pub version: u8,
+    pub user: Pubkey,
+    pub referrer: Pubkey,
+    pub code: [u8; 8],
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ReferralRewardsClaimed {
+    pub version: u8,
+    pub referrer: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+    pub total_claimed: u64,
+}
+
+#[event]
+pub struct ClaimDelegateSet {
+    pub version: u8,
+    pub user: Pubkey,
+    pub claim_delegate: Pubkey,
+    pub allow_permissionless_claim: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct UsersCheckpointed {
+    pub version: u8,
+    pub users_checkpointed: u32,
+    pub total_pending_rewards: u64,
+    pub cumulative_reward_per_token: u128,
+    pub timestamp: i64,
+}
+
+// -----------------------------------------------
+// Pool lifecycle and liquidity events
+//
+// All amounts use the token's native decimals, USD values and NAV use 6 decimals
+// and `sol_usd_price` is the 8-decimal Chainlink price the instruction used.
+// -----------------------------------------------
+
+#[event]
+pub struct PoolInitialized {
+    pub version: u8,
+    pub admin: Pubkey,
+    pub lp_token_mint: Pubkey,
+    pub sol_vault: Pubkey,
+    pub usdc_vault: Pubkey,
+    pub usdc_reward_vault: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PoolClosed {
+    pub version: u8,
+    pub admin: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct Deposited {
+    pub version: u8,
+    pub user: Pubkey,
+    pub vault: Pubkey,
+    pub amount: u64,
+    pub deposit_usd: u64,
+    pub lp_minted: u64,
+    /// NAV per LP the deposit was priced at
+    pub nav_per_lp: u64,
+    /// Composition fee kept by the pool, in USD
+    pub fee_usd: u64,
+    pub sol_usd_price: i128,
+    pub aum_before: u64,
+    pub aum_after: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct Withdrawn {
+    pub version: u8,
+    pub user: Pubkey,
+    pub vault: Pubkey,
+    pub amount: u64,
+    pub withdrawal_usd: u64,
+    pub lp_burned: u64,
+    /// NAV per LP the withdrawal was priced at
+    pub nav_per_lp: u64,
+    /// Composition fee kept by the pool, in USD
+    pub fee_usd: u64,
+    pub sol_usd_price: i128,
+    pub aum_before: u64,
+    pub aum_after: u64,
+    pub timestamp: i64,
+}
+
+/// In-kind exit, paid from both vaults without consulting the oracle
+#[event]
+pub struct ProportionalWithdrawn {
+    pub version: u8,
+    pub user: Pubkey,
+    pub sol_amount: u64,
+    pub usdc_amount: u64,
+    pub lp_burned: u64,
+    pub lp_supply_before: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct Swapped {
+    pub version: u8,
+    pub user: Pubkey,
+    /// True when selling SOL for USDC
+    pub sol_to_usdc: bool,
+    pub amount_in: u64,
+    pub amount_out: u64,
+    /// Spread kept by the pool, in USD
+    pub spread_usd: u64,
+    pub sol_usd_price: i128,
+    pub aum_before: u64,
+    pub aum_after: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AdminDeposited {
+    pub version: u8,
+    pub admin: Pubkey,
+    pub vault: Pubkey,
+    pub amount: u64,
+    /// NAV per LP after the deposit
+    pub nav_per_lp: u64,
+    pub sol_usd_price: i128,
+    pub aum_before: u64,
+    pub aum_after: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AdminWithdrawn {
+    pub version: u8,
+    pub admin: Pubkey,
+    pub vault: Pubkey,
+    pub amount: u64,
+    /// NAV per LP after the withdrawal
+    pub nav_per_lp: u64,
+    pub sol_usd_price: i128,
+    pub aum_before: u64,
+    pub aum_after: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RewardsStarted {
+    pub version: u8,
+    pub admin: Pubkey,
+    pub amount: u64,
+    /// New funds plus whatever the previous period had not emitted and any carried-over forfeits
+    pub period_amount: u64,
+    pub start_time: u64,
+    pub end_time: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct UserInitialized {
+    pub version: u8,
+    pub user: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct UserStateClosed {
+    pub version: u8,
+    pub user: Pubkey,
+    pub closed_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct UserStateForceClosed {
+    pub version: u8,
+    pub user: Pubkey,
+    pub admin: Pubkey,
+    pub timestamp: i64,
+}
+
+// -----------------------------------------------
+// Admin configuration events
+// -----------------------------------------------
+
+#[event]
+pub struct FeeConfigSet {
+    pub version: u8,
+    pub admin: Pubkey,
+    pub sol_target_weight_bps: u64,
+    pub base_fee_bps: u64,
+    pub tax_fee_bps: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SwapConfigSet {
+    pub version: u8,
+    pub admin: Pubkey,
+    pub swap_spread_bps: u64,
+    pub min_sol_weight_bps: u64,
+    pub max_sol_weight_bps: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PricePolicySet {
+    pub version: u8,
+    pub admin: Pubkey,
+    pub ema_window: u64,
+    pub price_policy: u8,
+    pub max_conf_bps: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ReferralShareSet {
+    pub version: u8,
+    pub admin: Pubkey,
+    pub referral_share_bps: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VestingConfigSet {
+    pub version: u8,
+    pub admin: Pubkey,
+    pub vesting_period: u64,
+    pub instant_claim_haircut_bps: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TreasurySet {
+    pub version: u8,
+    pub admin: Pubkey,
+    pub treasury: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct LpMetadataSet {
+    pub version: u8,
+    pub admin: Pubkey,
+    pub lp_token_mint: Pubkey,
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    pub timestamp: i64,
+}
@@ -1,10 +1,9 @@
 use crate::{
-    errors::VaultError, state::*, CHAINLINK_PROGRAM_ID, DEVNET_SOL_PRICE_FEED,
-    MAINNET_SOL_PRICE_FEED,
+    chainlink_program_id, errors::VaultError, instructions::helpers::*, sol_price_feed, state::*,
+    AdminWithdrawn, EVENT_SCHEMA_VERSION,
 };
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer};
-use chainlink_solana as chainlink;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
 
 #[derive(Accounts)]
 pub struct AdminWithdraw<'info> {
@@ -19,27 +18,39 @@ pub struct AdminWithdraw<'info> {
     )]
     pub pool_state: Account<'info, PoolState>,
 
-    #[account(mut)]
-    pub vault_account: Account<'info, TokenAccount>,
+    /// Vault for either SOL (wrapped) or USDC
+    #[account(
+        mut,
+        constraint = vault_account.key() == pool_state.sol_vault
+            || vault_account.key() == pool_state.usdc_vault @ VaultError::InvalidTokenMint
+    )]
+    pub vault_account: InterfaceAccount<'info, TokenAccount>,
 
-    #[account(mut)]
-    pub admin_token_account: Account<'info, TokenAccount>,
+    /// Mint of `vault_account`
+    #[account(constraint = vault_mint.key() == vault_account.mint @ VaultError::InvalidTokenMint)]
+    pub vault_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        constraint = admin_token_account.mint == vault_account.mint @ VaultError::InvalidTokenMint
+    )]
+    pub admin_token_account: InterfaceAccount<'info, TokenAccount>,
 
     /// CHECK: Validated in constraint
-    #[account(address = CHAINLINK_PROGRAM_ID.parse::<Pubkey>().unwrap())]
+    #[account(address = chainlink_program_id())]
     pub chainlink_program: AccountInfo<'info>,
 
     /// CHECK: Validated in constraint
+    #[account(address = sol_price_feed())]
+    pub chainlink_feed: AccountInfo<'info>,
+
+    /// LP token mint, read for the NAV reported in events
     #[account(
-        address = if cfg!(feature = "devnet") {
-            DEVNET_SOL_PRICE_FEED
-        } else {
-            MAINNET_SOL_PRICE_FEED
-        }.parse::<Pubkey>().unwrap()
+        constraint = lp_token_mint.key() == pool_state.lp_token_mint @ VaultError::InvalidTokenMint
     )]
-    pub chainlink_feed: AccountInfo<'info>,
+    pub lp_token_mint: InterfaceAccount<'info, Mint>,
 
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
 }
 
 pub fn handle_admin_withdraw(ctx: Context<AdminWithdraw>, amount: u64) -> Result<()> {
@@ -58,29 +69,33 @@ pub fn handle_admin_withdraw(ctx: Context<AdminWithdraw>, amount: u64) -> Result
         VaultError::Unauthorized
     );
 
-    // If withdrawing SOL, fetch/update the current SOL/USD price
-    if ctx.accounts.vault_account.key() == pool_state.sol_vault {
-        let round = chainlink::latest_round_data(
-            ctx.accounts.chainlink_program.to_account_info(),
-            ctx.accounts.chainlink_feed.to_account_info(),
-        )?;
-        pool_state.sol_usd_price = round.answer;
-    }
+    // Always refresh the SOL/USD price so the AUM in the event is current
+    let now = Clock::get()?.unix_timestamp;
+    let price = read_chainlink_price(
+        ctx.accounts.chainlink_program.to_account_info(),
+        ctx.accounts.chainlink_feed.to_account_info(),
+        now,
+    )?;
+    record_sol_price(pool_state, price, now)?;
+
+    let aum_before = calculate_aum(pool_state)?;
 
     // Transfer from the vault to the admin.
     // Notice we’re using `pool_state_info` (immutable AccountInfo)
     // as the authority for the vault’s PDA.
     let cpi_ctx = CpiContext::new(
         ctx.accounts.token_program.to_account_info(),
-        Transfer {
+        TransferChecked {
             from: ctx.accounts.vault_account.to_account_info(),
+            mint: ctx.accounts.vault_mint.to_account_info(),
             to: ctx.accounts.admin_token_account.to_account_info(),
             authority: pool_state_info,
         },
     );
-    token::transfer(
+    token_interface::transfer_checked(
         cpi_ctx.with_signer(&[&[b"pool-state".as_ref(), &[ctx.bumps.pool_state]]]),
         amount,
+        ctx.accounts.vault_mint.decimals,
     )?;
 
     // Decrement deposited tokens
@@ -98,6 +113,19 @@ pub fn handle_admin_withdraw(ctx: Context<AdminWithdraw>, amount: u64) -> Result
         return err!(VaultError::InvalidTokenMint);
     }
 
+    let aum_after = calculate_aum(pool_state)?;
+    emit!(AdminWithdrawn {
+        version: EVENT_SCHEMA_VERSION,
+        admin: ctx.accounts.admin.key(),
+        vault: ctx.accounts.vault_account.key(),
+        amount,
+        nav_per_lp: calculate_nav_per_lp(aum_after, ctx.accounts.lp_token_mint.supply)?,
+        sol_usd_price: pool_state.sol_usd_price,
+        aum_before,
+        aum_after,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
     msg!("Admin withdrew {} tokens from vault.", amount);
     Ok(())
 }
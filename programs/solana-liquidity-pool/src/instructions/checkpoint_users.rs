@@ -0,0 +1,73 @@
+use crate::instructions::helpers::update_rewards;
+use crate::state::*;
+use crate::{errors::VaultError, UsersCheckpointed, EVENT_SCHEMA_VERSION};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct CheckpointUsers<'info> {
+    #[account(
+        mut,
+        seeds = [b"pool-state".as_ref()],
+        bump
+    )]
+    pub pool_state: Account<'info, PoolState>,
+    // Writable `user-state` PDAs to settle are passed as remaining accounts
+}
+
+/// Permissionlessly settle `pending_rewards` for every user state in `remaining_accounts`.
+pub fn handle_checkpoint_users<'info>(
+    ctx: Context<'_, '_, 'info, 'info, CheckpointUsers<'info>>,
+) -> Result<()> {
+    let pool_state = &mut ctx.accounts.pool_state;
+    let mut users_checkpointed: u32 = 0;
+    let mut total_pending_rewards: u64 = 0;
+
+    for (i, account_info) in ctx.remaining_accounts.iter().enumerate() {
+        require!(account_info.is_writable, VaultError::InvalidUserState);
+        require!(
+            ctx.remaining_accounts[..i]
+                .iter()
+                .all(|earlier| earlier.key != account_info.key),
+            VaultError::DuplicateUserState
+        );
+
+        // Checks the owning program and discriminator
+        let mut user_state: Account<UserState> = Account::try_from(account_info)?;
+
+        // Only canonical user-state PDAs may be settled
+        let (expected_key, _) = Pubkey::find_program_address(
+            &[b"user-state".as_ref(), user_state.owner.as_ref()],
+            ctx.program_id,
+        );
+        require_keys_eq!(
+            expected_key,
+            account_info.key(),
+            VaultError::InvalidUserState
+        );
+
+        update_rewards(pool_state, &mut user_state)?;
+        user_state.exit(ctx.program_id)?;
+
+        users_checkpointed = users_checkpointed
+            .checked_add(1)
+            .ok_or(VaultError::MathError)?;
+        total_pending_rewards = total_pending_rewards
+            .checked_add(user_state.pending_rewards)
+            .ok_or(VaultError::MathError)?;
+    }
+
+    emit!(UsersCheckpointed {
+        version: EVENT_SCHEMA_VERSION,
+        users_checkpointed,
+        total_pending_rewards,
+        cumulative_reward_per_token: pool_state.cumulative_reward_per_token,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!(
+        "Checkpointed {} users with {} USDC pending in total.",
+        users_checkpointed,
+        total_pending_rewards
+    );
+    Ok(())
+}
@@ -0,0 +1,123 @@
+use crate::instructions::helpers::{merkle_leaf, verify_merkle_proof};
+use crate::state::*;
+use crate::{errors::VaultError, MerkleRewardsClaimed, EVENT_SCHEMA_VERSION};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+
+#[derive(Accounts)]
+pub struct ClaimMerkleRewards<'info> {
+    #[account(mut)]
+    pub claimant: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"merkle-distributor".as_ref(),
+            distributor.campaign_id.to_le_bytes().as_ref()
+        ],
+        bump = distributor.bump
+    )]
+    pub distributor: Account<'info, MerkleDistributor>,
+
+    #[account(
+        mut,
+        seeds = [b"claim-bitmap".as_ref(), distributor.key().as_ref()],
+        bump
+    )]
+    pub claim_bitmap: Account<'info, ClaimBitmap>,
+
+    #[account(
+        mut,
+        constraint = vault.key() == distributor.vault
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Mint of the campaign token
+    #[account(constraint = mint.key() == distributor.mint @ VaultError::InvalidTokenMint)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        constraint = claimant_token_account.owner == claimant.key(),
+        constraint = claimant_token_account.mint == distributor.mint @ VaultError::InvalidTokenMint
+    )]
+    pub claimant_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn handle_claim_merkle_rewards(
+    ctx: Context<ClaimMerkleRewards>,
+    index: u64,
+    amount: u64,
+    proof: Vec<[u8; 32]>,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let claimant = ctx.accounts.claimant.key();
+    let distributor = &mut ctx.accounts.distributor;
+    let claim_bitmap = &mut ctx.accounts.claim_bitmap;
+
+    require!(
+        !distributor.clawed_back && (now as u64) < distributor.expiry_time,
+        VaultError::DistributorExpired
+    );
+    require!(
+        index < distributor.num_claims,
+        VaultError::InvalidClaimIndex
+    );
+    require!(!claim_bitmap.is_claimed(index), VaultError::AlreadyClaimed);
+
+    // The leaf binds the index, claimant and amount together
+    let leaf = merkle_leaf(index, &claimant, amount);
+    require!(
+        verify_merkle_proof(&proof, distributor.merkle_root, leaf),
+        VaultError::InvalidMerkleProof
+    );
+
+    claim_bitmap.set_claimed(index);
+
+    // Transfer from the campaign vault, signed by the distributor PDA
+    let campaign_id_bytes = distributor.campaign_id.to_le_bytes();
+    let signer_seeds: &[&[u8]] = &[
+        b"merkle-distributor".as_ref(),
+        campaign_id_bytes.as_ref(),
+        &[distributor.bump],
+    ];
+    let cpi_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        TransferChecked {
+            from: ctx.accounts.vault.to_account_info(),
+            mint: ctx.accounts.mint.to_account_info(),
+            to: ctx.accounts.claimant_token_account.to_account_info(),
+            authority: distributor.to_account_info(),
+        },
+    );
+    token_interface::transfer_checked(
+        cpi_ctx.with_signer(&[signer_seeds]),
+        amount,
+        ctx.accounts.mint.decimals,
+    )?;
+
+    distributor.total_claimed = distributor
+        .total_claimed
+        .checked_add(amount)
+        .ok_or(VaultError::MathError)?;
+
+    // Emit event for subgraph indexing
+    emit!(MerkleRewardsClaimed {
+        version: EVENT_SCHEMA_VERSION,
+        campaign_id: distributor.campaign_id,
+        user: claimant,
+        amount,
+        timestamp: now,
+        total_claimed: distributor.total_claimed,
+    });
+
+    msg!(
+        "User {} claimed {} tokens from campaign {}.",
+        claimant,
+        amount,
+        distributor.campaign_id
+    );
+    Ok(())
+}
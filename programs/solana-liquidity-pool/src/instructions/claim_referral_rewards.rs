@@ -0,0 +1,139 @@
+use crate::instructions::helpers::update_rewards;
+use crate::state::*;
+use crate::{errors::VaultError, ReferralRewardsClaimed, EVENT_SCHEMA_VERSION};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+
+#[derive(Accounts)]
+pub struct ClaimReferralRewards<'info> {
+    #[account(mut)]
+    pub referrer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"referrer".as_ref(), referrer_state.code.as_ref()],
+        bump,
+        constraint = referrer_state.owner == referrer.key() @ VaultError::InvalidOwner
+    )]
+    pub referrer_state: Account<'info, ReferrerState>,
+
+    #[account(
+        mut,
+        seeds = [b"pool-state".as_ref()],
+        bump
+    )]
+    pub pool_state: Account<'info, PoolState>,
+
+    #[account(
+        mut,
+        constraint = usdc_reward_vault.key() == pool_state.usdc_reward_vault,
+        constraint = usdc_reward_vault.owner == pool_state.key() @ VaultError::InvalidOwner
+    )]
+    pub usdc_reward_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Mint of the USDC reward vault
+    #[account(constraint = usdc_mint.key() == usdc_reward_vault.mint @ VaultError::InvalidTokenMint)]
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        constraint = referrer_usdc_account.owner == referrer.key(),
+        constraint = referrer_usdc_account.mint == usdc_reward_vault.mint
+    )]
+    pub referrer_usdc_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    // Writable `user-state` PDAs of referees to collect from are passed as remaining accounts
+}
+
+/// Collect the referral share owed by each referee in `remaining_accounts`, then pay out
+/// everything the referrer has accrued.
+pub fn handle_claim_referral_rewards<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ClaimReferralRewards<'info>>,
+) -> Result<()> {
+    let pool_state = &mut ctx.accounts.pool_state;
+    let referrer_state = &mut ctx.accounts.referrer_state;
+
+    // 1) Settle each referee and move what they owe into the referrer's balance
+    for account_info in ctx.remaining_accounts.iter() {
+        require!(account_info.is_writable, VaultError::InvalidUserState);
+
+        let mut user_state: Account<UserState> = Account::try_from(account_info)?;
+        let (expected_key, _) = Pubkey::find_program_address(
+            &[b"user-state".as_ref(), user_state.owner.as_ref()],
+            ctx.program_id,
+        );
+        require_keys_eq!(
+            expected_key,
+            account_info.key(),
+            VaultError::InvalidUserState
+        );
+        require_keys_eq!(
+            user_state.referrer,
+            referrer_state.key(),
+            VaultError::InvalidUserState
+        );
+
+        update_rewards(pool_state, &mut user_state)?;
+        referrer_state.pending_rewards = referrer_state
+            .pending_rewards
+            .checked_add(user_state.referral_rewards_owed)
+            .ok_or(VaultError::MathError)?;
+        user_state.referral_rewards_owed = 0;
+        user_state.exit(ctx.program_id)?;
+    }
+
+    // 2) Pay out as much as the reward pool can cover
+    let available = pool_state
+        .total_rewards_deposited
+        .saturating_sub(pool_state.total_rewards_claimed);
+    let to_claim = referrer_state.pending_rewards.min(available);
+    if to_claim == 0 {
+        msg!("No referral rewards to claim.");
+        return Ok(());
+    }
+    require!(
+        ctx.accounts.usdc_reward_vault.amount >= to_claim,
+        VaultError::InsufficientRewardBalance
+    );
+
+    let cpi_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        TransferChecked {
+            from: ctx.accounts.usdc_reward_vault.to_account_info(),
+            mint: ctx.accounts.usdc_mint.to_account_info(),
+            to: ctx.accounts.referrer_usdc_account.to_account_info(),
+            authority: pool_state.to_account_info(),
+        },
+    );
+    token_interface::transfer_checked(
+        cpi_ctx.with_signer(&[&[b"pool-state".as_ref(), &[ctx.bumps.pool_state]]]),
+        to_claim,
+        ctx.accounts.usdc_mint.decimals,
+    )?;
+
+    pool_state.total_rewards_claimed = pool_state
+        .total_rewards_claimed
+        .checked_add(to_claim)
+        .ok_or(VaultError::MathError)?;
+    referrer_state.pending_rewards -= to_claim;
+    referrer_state.total_claimed = referrer_state
+        .total_claimed
+        .checked_add(to_claim)
+        .ok_or(VaultError::MathError)?;
+
+    emit!(ReferralRewardsClaimed {
+        version: EVENT_SCHEMA_VERSION,
+        referrer: referrer_state.owner,
+        amount: to_claim,
+        timestamp: Clock::get()?.unix_timestamp,
+        total_claimed: referrer_state.total_claimed,
+    });
+
+    msg!(
+        "Referrer {} claimed {} USDC in referral rewards.",
+        referrer_state.owner,
+        to_claim
+    );
+    Ok(())
+}
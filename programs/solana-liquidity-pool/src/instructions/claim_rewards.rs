@@ -1,8 +1,9 @@
-use crate::instructions::helpers::update_rewards;
+use crate::instructions::helpers::{redistribute_rewards, update_rewards};
 use crate::state::*;
-use crate::{errors::VaultError, RewardsClaimed};
+use crate::{errors::VaultError, RewardsClaimed, EVENT_SCHEMA_VERSION};
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
 
 #[derive(Accounts)]
 pub struct ClaimRewards<'info> {
@@ -29,53 +30,79 @@ pub struct ClaimRewards<'info> {
         constraint = usdc_reward_vault.key() == pool_state.usdc_reward_vault,
         constraint = usdc_reward_vault.owner == pool_state.key() @ VaultError::InvalidOwner
     )]
-    pub usdc_reward_vault: Account<'info, TokenAccount>,
+    pub usdc_reward_vault: InterfaceAccount<'info, TokenAccount>,
 
+    /// Mint of the USDC reward vault
+    #[account(constraint = usdc_mint.key() == usdc_reward_vault.mint @ VaultError::InvalidTokenMint)]
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+
+    /// The user's USDC account, created on first claim
     #[account(
-        mut,
-        constraint = user_usdc_account.owner == user.key(),
-        constraint = user_usdc_account.mint == usdc_reward_vault.mint
+        init_if_needed,
+        payer = user,
+        associated_token::mint = usdc_mint,
+        associated_token::authority = user,
+        associated_token::token_program = token_program
     )]
-    pub user_usdc_account: Account<'info, TokenAccount>,
+    pub user_usdc_account: InterfaceAccount<'info, TokenAccount>,
 
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
 
     #[account(
         constraint = lp_token_mint.key() == pool_state.lp_token_mint @ VaultError::InvalidTokenMint
     )]
-    pub lp_token_mint: Account<'info, Mint>,
+    pub lp_token_mint: InterfaceAccount<'info, Mint>,
 }
 
 pub fn handle_claim_rewards(ctx: Context<ClaimRewards>) -> Result<()> {
+    let accounts = ctx.accounts;
+    pay_out_rewards(
+        &mut accounts.pool_state,
+        ctx.bumps.pool_state,
+        &mut accounts.user_state,
+        &accounts.usdc_reward_vault,
+        &accounts.usdc_mint,
+        &accounts.user_usdc_account,
+        &accounts.token_program,
+    )
+}
+
+/// Settle `user_state` and pay its pending rewards to `user_usdc_account`, which the
+/// calling context must constrain to the user state's owner.
+pub fn pay_out_rewards<'info>(
+    pool_state: &mut Account<'info, PoolState>,
+    pool_state_bump: u8,
+    user_state: &mut Account<'info, UserState>,
+    usdc_reward_vault: &InterfaceAccount<'info, TokenAccount>,
+    usdc_mint: &InterfaceAccount<'info, Mint>,
+    user_usdc_account: &InterfaceAccount<'info, TokenAccount>,
+    token_program: &Interface<'info, TokenInterface>,
+) -> Result<()> {
     // Store validation values up front
     let now = Clock::get()?.unix_timestamp as u64;
-    let reward_start_time = ctx.accounts.pool_state.reward_start_time;
-    let total_rewards_deposited = ctx.accounts.pool_state.total_rewards_deposited;
-    let total_rewards_claimed = ctx.accounts.pool_state.total_rewards_claimed;
-    let vault_amount = ctx.accounts.usdc_reward_vault.amount;
-    let pool_state_bump = ctx.bumps.pool_state;
-
-    // Do validation checks with stored values
-    require!(now >= reward_start_time, VaultError::RewardsNotStarted);
+    let owner = user_state.owner;
 
+    // Do validation checks
     require!(
-        ctx.accounts.user_state.lp_token_balance > 0,
-        VaultError::NoLPTokens
+        now >= pool_state.reward_start_time,
+        VaultError::RewardsNotStarted
     );
 
-    let available = total_rewards_deposited.saturating_sub(total_rewards_claimed);
+    require!(user_state.lp_token_balance > 0, VaultError::NoLPTokens);
+
+    let available = pool_state
+        .total_rewards_deposited
+        .saturating_sub(pool_state.total_rewards_claimed);
 
     require!(
-        vault_amount >= available,
+        usdc_reward_vault.amount >= available,
         VaultError::InsufficientRewardBalance
     );
 
-    // Now take mutable references for state updates
-    let pool_state = &mut ctx.accounts.pool_state;
-    let user_state = &mut ctx.accounts.user_state;
-
     // 1) Update user’s accrual to get an up-to-date `pending_rewards`
-    update_rewards(pool_state, user_state, &ctx.accounts.lp_token_mint)?;
+    update_rewards(pool_state, user_state)?;
 
     // 2) The user now has some "pending" amount stored locally
     let pending = user_state.pending_rewards;
@@ -89,13 +116,6 @@ pub fn handle_claim_rewards(ctx: Context<ClaimRewards>) -> Result<()> {
         .total_rewards_deposited
         .saturating_sub(pool_state.total_rewards_claimed);
 
-    // Add vault balance check
-    let vault_balance = ctx.accounts.usdc_reward_vault.amount;
-    require!(
-        vault_balance >= available,
-        VaultError::InsufficientRewardBalance
-    );
-
     // Clamp the user’s claim if not enough remains in the reward pool
     let to_claim = pending.min(available);
     if to_claim == 0 {
@@ -103,24 +123,38 @@ pub fn handle_claim_rewards(ctx: Context<ClaimRewards>) -> Result<()> {
         return Ok(());
     }
 
-    // 4) Transfer `to_claim` tokens from the reward vault to the user
+    // With vesting enabled, claiming instantly forfeits a haircut to the other stakers
+    let haircut = if pool_state.vesting_period > 0 {
+        (to_claim as u128)
+            .checked_mul(pool_state.instant_claim_haircut_bps as u128)
+            .ok_or(VaultError::MathError)?
+            .checked_div(BPS_DENOMINATOR as u128)
+            .ok_or(VaultError::MathError)? as u64
+    } else {
+        0
+    };
+    let payout = to_claim.checked_sub(haircut).ok_or(VaultError::MathError)?;
+
+    // 4) Transfer `payout` tokens from the reward vault to the user
     let cpi_ctx = CpiContext::new(
-        ctx.accounts.token_program.to_account_info(),
-        Transfer {
-            from: ctx.accounts.usdc_reward_vault.to_account_info(),
-            to: ctx.accounts.user_usdc_account.to_account_info(),
-            authority: pool_state.to_account_info(), // Use pool_state reference instead
+        token_program.to_account_info(),
+        TransferChecked {
+            from: usdc_reward_vault.to_account_info(),
+            mint: usdc_mint.to_account_info(),
+            to: user_usdc_account.to_account_info(),
+            authority: pool_state.to_account_info(),
         },
     );
-    token::transfer(
+    token_interface::transfer_checked(
         cpi_ctx.with_signer(&[&[b"pool-state".as_ref(), &[pool_state_bump]]]),
-        to_claim,
+        payout,
+        usdc_mint.decimals,
     )?;
 
     // 5) Update global and user-level state
     pool_state.total_rewards_claimed = pool_state
         .total_rewards_claimed
-        .checked_add(to_claim)
+        .checked_add(payout)
         .ok_or_else(|| error!(VaultError::MathError))?;
 
     user_state.pending_rewards = user_state
@@ -128,18 +162,24 @@ pub fn handle_claim_rewards(ctx: Context<ClaimRewards>) -> Result<()> {
         .checked_sub(to_claim)
         .ok_or_else(|| error!(VaultError::MathError))?;
 
+    // The forfeited haircut stays in the reward vault and is owed to everyone else
+    if haircut > 0 {
+        msg!(
+            "Instant claim haircut of {} USDC returned to the reward pool",
+            haircut
+        );
+        redistribute_rewards(pool_state, user_state, haircut)?;
+    }
+
     // Emit event for subgraph indexing
     emit!(RewardsClaimed {
-        user: ctx.accounts.user.key(),
-        amount: to_claim,
-        timestamp: Clock::get()?.unix_timestamp,
+        version: EVENT_SCHEMA_VERSION,
+        user: owner,
+        amount: payout,
+        timestamp: now as i64,
         total_claimed: pool_state.total_rewards_claimed,
     });
 
-    msg!(
-        "User {} claimed {} USDC in rewards.",
-        ctx.accounts.user.key(),
-        to_claim
-    );
+    msg!("User {} claimed {} USDC in rewards.", owner, payout);
     Ok(())
 }
@@ -0,0 +1,90 @@
+use crate::errors::VaultError;
+use crate::instructions::claim_rewards::pay_out_rewards;
+use crate::state::*;
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+#[derive(Accounts)]
+pub struct ClaimRewardsFor<'info> {
+    /// The owner, their claim delegate, or anyone if the owner allows permissionless claims
+    #[account(mut)]
+    pub caller: Signer<'info>,
+
+    /// CHECK: Only used for PDA derivation and payout account validation
+    pub owner: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"pool-state".as_ref()],
+        bump
+    )]
+    pub pool_state: Account<'info, PoolState>,
+
+    #[account(
+        mut,
+        seeds = [b"user-state".as_ref(), owner.key().as_ref()],
+        bump,
+        constraint = user_state.owner == owner.key() @ VaultError::InvalidOwner,
+        constraint = (
+            caller.key() == owner.key()
+                || caller.key() == user_state.claim_delegate
+                || user_state.allow_permissionless_claim
+        ) @ VaultError::Unauthorized
+    )]
+    pub user_state: Account<'info, UserState>,
+
+    #[account(
+        mut,
+        constraint = usdc_reward_vault.key() == pool_state.usdc_reward_vault,
+        constraint = usdc_reward_vault.owner == pool_state.key() @ VaultError::InvalidOwner
+    )]
+    pub usdc_reward_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Mint of the USDC reward vault
+    #[account(constraint = usdc_mint.key() == usdc_reward_vault.mint @ VaultError::InvalidTokenMint)]
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+
+    /// Rewards always go to the owner's USDC account, never the caller's; the caller pays to create it
+    #[account(
+        init_if_needed,
+        payer = caller,
+        associated_token::mint = usdc_mint,
+        associated_token::authority = owner,
+        associated_token::token_program = token_program
+    )]
+    pub owner_usdc_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Claim rewards on behalf of `owner`, paying out to the owner's USDC account.
+pub fn handle_claim_rewards_for(ctx: Context<ClaimRewardsFor>) -> Result<()> {
+    msg!(
+        "{} claiming rewards on behalf of {}",
+        ctx.accounts.caller.key(),
+        ctx.accounts.owner.key()
+    );
+
+    // Nobody else may opt the owner into forfeiting an instant-claim haircut
+    let pool_state = &ctx.accounts.pool_state;
+    require!(
+        ctx.accounts.caller.key() == ctx.accounts.owner.key()
+            || pool_state.vesting_period == 0
+            || pool_state.instant_claim_haircut_bps == 0,
+        VaultError::DelegatedClaimHaircut
+    );
+
+    let accounts = ctx.accounts;
+    pay_out_rewards(
+        &mut accounts.pool_state,
+        ctx.bumps.pool_state,
+        &mut accounts.user_state,
+        &accounts.usdc_reward_vault,
+        &accounts.usdc_mint,
+        &accounts.owner_usdc_account,
+        &accounts.token_program,
+    )
+}
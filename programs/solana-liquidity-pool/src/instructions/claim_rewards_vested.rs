@@ -0,0 +1,101 @@
+use crate::instructions::helpers::update_rewards;
+use crate::state::*;
+use crate::{errors::VaultError, RewardsVested, EVENT_SCHEMA_VERSION};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct ClaimRewardsVested<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"pool-state".as_ref()],
+        bump
+    )]
+    pub pool_state: Account<'info, PoolState>,
+
+    #[account(
+        mut,
+        seeds = [b"user-state".as_ref(), user.key().as_ref()],
+        bump,
+        constraint = user_state.owner == user.key()
+    )]
+    pub user_state: Account<'info, UserState>,
+
+    /// The user's vesting schedule, created on the first vested claim
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + VestingState::LEN,
+        seeds = [b"vesting".as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub vesting_state: Account<'info, VestingState>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Move the user's pending rewards into their vesting schedule instead of paying them out.
+/// The USDC stays in the reward vault until `withdraw_vested`.
+pub fn handle_claim_rewards_vested(ctx: Context<ClaimRewardsVested>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp as u64;
+    let pool_state = &mut ctx.accounts.pool_state;
+    let user_state = &mut ctx.accounts.user_state;
+    let vesting_state = &mut ctx.accounts.vesting_state;
+
+    require!(pool_state.vesting_period > 0, VaultError::VestingDisabled);
+    require!(
+        now >= pool_state.reward_start_time,
+        VaultError::RewardsNotStarted
+    );
+    require!(user_state.lp_token_balance > 0, VaultError::NoLPTokens);
+
+    // 1) Update user’s accrual to get an up-to-date `pending_rewards`
+    update_rewards(pool_state, user_state)?;
+
+    // 2) Clamp to what is still available in the reward pool
+    let available = pool_state
+        .total_rewards_deposited
+        .saturating_sub(pool_state.total_rewards_claimed);
+    let to_vest = user_state.pending_rewards.min(available);
+    if to_vest == 0 {
+        msg!("No rewards to vest.");
+        return Ok(());
+    }
+
+    // 3) Add to the schedule (a new schedule starts with an empty account)
+    vesting_state.owner = ctx.accounts.user.key();
+    vesting_state.extend(to_vest, now, pool_state.vesting_period)?;
+
+    // 4) Vested rewards count as claimed; the vault now owes them to the schedule
+    pool_state.total_rewards_claimed = pool_state
+        .total_rewards_claimed
+        .checked_add(to_vest)
+        .ok_or(VaultError::MathError)?;
+    pool_state.total_rewards_vesting = pool_state
+        .total_rewards_vesting
+        .checked_add(to_vest)
+        .ok_or(VaultError::MathError)?;
+    user_state.pending_rewards = user_state
+        .pending_rewards
+        .checked_sub(to_vest)
+        .ok_or(VaultError::MathError)?;
+
+    msg!(
+        "User {} vesting {} USDC until {}.",
+        ctx.accounts.user.key(),
+        vesting_state.total_amount,
+        vesting_state.end_time
+    );
+
+    emit!(RewardsVested {
+        version: EVENT_SCHEMA_VERSION,
+        user: ctx.accounts.user.key(),
+        amount: to_vest,
+        vesting_total: vesting_state.total_amount,
+        vesting_end_time: vesting_state.end_time,
+        timestamp: now as i64,
+    });
+    Ok(())
+}
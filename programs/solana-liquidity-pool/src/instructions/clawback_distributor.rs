@@ -0,0 +1,95 @@
+use crate::{errors::VaultError, state::*, DistributorClawedBack, EVENT_SCHEMA_VERSION};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+
+#[derive(Accounts)]
+pub struct ClawbackDistributor<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"pool-state".as_ref()],
+        bump,
+        constraint = pool_state.admin == admin.key() @ VaultError::Unauthorized
+    )]
+    pub pool_state: Account<'info, PoolState>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"merkle-distributor".as_ref(),
+            distributor.campaign_id.to_le_bytes().as_ref()
+        ],
+        bump = distributor.bump
+    )]
+    pub distributor: Account<'info, MerkleDistributor>,
+
+    #[account(
+        mut,
+        constraint = vault.key() == distributor.vault
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Mint of the campaign token
+    #[account(constraint = mint.key() == distributor.mint @ VaultError::InvalidTokenMint)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// Admin's token account receiving the unclaimed funds
+    #[account(
+        mut,
+        constraint = admin_token_account.mint == distributor.mint @ VaultError::InvalidTokenMint
+    )]
+    pub admin_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn handle_clawback_distributor(ctx: Context<ClawbackDistributor>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp as u64;
+    let distributor = &mut ctx.accounts.distributor;
+
+    require!(
+        now >= distributor.expiry_time,
+        VaultError::DistributorNotExpired
+    );
+
+    let amount = ctx.accounts.vault.amount;
+    if amount > 0 {
+        let campaign_id_bytes = distributor.campaign_id.to_le_bytes();
+        let signer_seeds: &[&[u8]] = &[
+            b"merkle-distributor".as_ref(),
+            campaign_id_bytes.as_ref(),
+            &[distributor.bump],
+        ];
+        let cpi_ctx = CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.vault.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
+                to: ctx.accounts.admin_token_account.to_account_info(),
+                authority: distributor.to_account_info(),
+            },
+        );
+        token_interface::transfer_checked(
+            cpi_ctx.with_signer(&[signer_seeds]),
+            amount,
+            ctx.accounts.mint.decimals,
+        )?;
+    }
+
+    distributor.clawed_back = true;
+
+    msg!(
+        "Clawed back {} unclaimed tokens from campaign {}.",
+        amount,
+        distributor.campaign_id
+    );
+
+    emit!(DistributorClawedBack {
+        version: EVENT_SCHEMA_VERSION,
+        campaign_id: distributor.campaign_id,
+        amount,
+        timestamp: now as i64,
+    });
+    Ok(())
+}
@@ -1,4 +1,4 @@
-use crate::{errors::VaultError, state::*};
+use crate::{errors::VaultError, state::*, PoolClosed, EVENT_SCHEMA_VERSION};
 use anchor_lang::prelude::*;
 
 #[derive(Accounts)]
@@ -26,6 +26,12 @@ pub fn handle_close_pool(ctx: Context<ClosePool>) -> Result<()> {
         VaultError::Unauthorized
     );
 
+    emit!(PoolClosed {
+        version: EVENT_SCHEMA_VERSION,
+        admin: ctx.accounts.admin.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
     // The account will be automatically closed and rent returned to admin
     // because of the `close = admin` constraint
     msg!("Pool state account closed successfully");
@@ -1,4 +1,4 @@
-use crate::{errors::VaultError, state::*};
+use crate::{errors::VaultError, state::*, UserStateClosed, EVENT_SCHEMA_VERSION};
 use anchor_lang::prelude::*;
 
 #[derive(Accounts)]
@@ -26,6 +26,32 @@ pub struct CloseUserState<'info> {
 }
 
 pub fn handle_close_user_state(ctx: Context<CloseUserState>) -> Result<()> {
+    // Closing would erase what the referrer is still owed
+    let user_state = &ctx.accounts.user_state;
+    require!(
+        user_state.referral_rewards_owed == 0,
+        VaultError::ReferralRewardsOutstanding
+    );
+    // ...or the user's own escrowed LP and unclaimed rewards
+    require!(user_state.locked_lp_amount == 0, VaultError::LpTokensLocked);
+    require!(
+        user_state.pending_rewards == 0,
+        VaultError::PendingRewardsOutstanding
+    );
+
+    // Stop counting the closed account's weight in the reward denominator
+    let pool_state = &mut ctx.accounts.pool_state;
+    pool_state.total_boosted_supply = pool_state
+        .total_boosted_supply
+        .saturating_sub(ctx.accounts.user_state.boosted_balance);
+
+    emit!(UserStateClosed {
+        version: EVENT_SCHEMA_VERSION,
+        user: ctx.accounts.user_state.owner,
+        closed_by: ctx.accounts.user.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
     // Log who is closing the account
     if ctx.accounts.user.key() == ctx.accounts.pool_state.admin {
         msg!(
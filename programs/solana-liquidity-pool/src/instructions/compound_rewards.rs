@@ -0,0 +1,209 @@
+use crate::instructions::helpers::*;
+use crate::state::*;
+use crate::{
+    chainlink_program_id, errors::VaultError, pyth_sol_price_feed, sol_price_feed, RewardsClaimed,
+    EVENT_SCHEMA_VERSION,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{
+    self, Mint, MintTo, TokenAccount, TokenInterface, TransferChecked,
+};
+
+#[derive(Accounts)]
+pub struct CompoundRewards<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"pool-state".as_ref()],
+        bump
+    )]
+    pub pool_state: Account<'info, PoolState>,
+
+    #[account(
+        mut,
+        seeds = [b"user-state".as_ref(), user.key().as_ref()],
+        bump,
+        constraint = user_state.owner == user.key()
+    )]
+    pub user_state: Account<'info, UserState>,
+
+    #[account(
+        mut,
+        constraint = usdc_reward_vault.key() == pool_state.usdc_reward_vault,
+        constraint = usdc_reward_vault.owner == pool_state.key() @ VaultError::InvalidOwner
+    )]
+    pub usdc_reward_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Mint of the USDC reward vault
+    #[account(constraint = usdc_mint.key() == usdc_reward_vault.mint @ VaultError::InvalidTokenMint)]
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+
+    /// Pool USDC vault the compounded rewards are deposited into
+    #[account(
+        mut,
+        constraint = usdc_vault.key() == pool_state.usdc_vault
+    )]
+    pub usdc_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// LP token mint
+    #[account(
+        mut,
+        constraint = lp_token_mint.key() == pool_state.lp_token_mint @ VaultError::InvalidTokenMint
+    )]
+    pub lp_token_mint: InterfaceAccount<'info, Mint>,
+
+    /// The user's LP token account (where minted LP tokens will go)
+    #[account(
+        mut,
+        constraint = user_lp_token_account.owner == user.key(),
+        constraint = user_lp_token_account.mint == lp_token_mint.key()
+    )]
+    pub user_lp_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: Validated in constraint
+    #[account(address = chainlink_program_id())]
+    pub chainlink_program: AccountInfo<'info>,
+
+    /// CHECK: Validated in constraint
+    #[account(address = sol_price_feed())]
+    pub chainlink_feed: AccountInfo<'info>,
+
+    /// CHECK: Validated in constraint, parsed in `read_pyth_conf_bps`
+    #[account(address = pyth_sol_price_feed())]
+    pub pyth_price_feed: AccountInfo<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Claim the user's pending rewards straight into the USDC vault and mint LP for them
+/// at the current NAV, net of the composition fee, exactly as a USDC `deposit` of the
+/// same amount would.
+pub fn handle_compound_rewards(ctx: Context<CompoundRewards>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp as u64;
+    let pool_state_bump = ctx.bumps.pool_state;
+    let pool_state = &mut ctx.accounts.pool_state;
+    let user_state = &mut ctx.accounts.user_state;
+
+    require!(
+        now >= pool_state.reward_start_time,
+        VaultError::RewardsNotStarted
+    );
+    require!(user_state.lp_token_balance > 0, VaultError::NoLPTokens);
+
+    // 1) Update user’s accrual to get an up-to-date `pending_rewards`
+    update_rewards(pool_state, user_state)?;
+
+    // 2) Clamp to what is still available in the reward pool
+    let available = pool_state
+        .total_rewards_deposited
+        .saturating_sub(pool_state.total_rewards_claimed);
+    let to_compound = user_state.pending_rewards.min(available);
+    if to_compound == 0 {
+        msg!("No rewards to compound.");
+        return Ok(());
+    }
+    require!(
+        ctx.accounts.usdc_reward_vault.amount >= to_compound,
+        VaultError::InsufficientRewardBalance
+    );
+
+    // 3) Move the rewards from the reward vault into the USDC vault
+    let usdc_vault_before = ctx.accounts.usdc_vault.amount;
+    let cpi_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        TransferChecked {
+            from: ctx.accounts.usdc_reward_vault.to_account_info(),
+            mint: ctx.accounts.usdc_mint.to_account_info(),
+            to: ctx.accounts.usdc_vault.to_account_info(),
+            authority: pool_state.to_account_info(),
+        },
+    );
+    token_interface::transfer_checked(
+        cpi_ctx.with_signer(&[&[b"pool-state".as_ref(), &[pool_state_bump]]]),
+        to_compound,
+        ctx.accounts.usdc_mint.decimals,
+    )?;
+
+    // Only what actually arrived (net of any transfer fee) joins the pool
+    ctx.accounts.usdc_vault.reload()?;
+    let received = ctx
+        .accounts
+        .usdc_vault
+        .amount
+        .checked_sub(usdc_vault_before)
+        .ok_or(VaultError::MathError)?;
+
+    // 4) Price the LP against the AUM before the rewards join the pool, valued high
+    //    like any other mint at a freshly refreshed price
+    let price = read_chainlink_price(
+        ctx.accounts.chainlink_program.to_account_info(),
+        ctx.accounts.chainlink_feed.to_account_info(),
+        now as i64,
+    )?;
+    record_sol_price(pool_state, price, now as i64)?;
+    let conf_bps = read_pyth_conf_bps(&ctx.accounts.pyth_price_feed, now as i64)?;
+    record_price_confidence(pool_state, conf_bps)?;
+    msg!("Updated SOL/USD price to {} (8 dec)", price);
+    let (_, high_price) = sol_price_range(pool_state);
+    let initial_aum = calculate_aum_at_price(pool_state, high_price)?;
+    msg!("Initial total AUM: {} (6 dec)", initial_aum);
+    let lp_supply = ctx.accounts.lp_token_mint.supply;
+    let (lp_to_mint, fee_usd) =
+        calculate_deposit_lp(pool_state, false, received, lp_supply, initial_aum)?;
+    msg!(
+        "Will mint {} LP tokens (6 dec) after a {} USD fee",
+        lp_to_mint,
+        fee_usd
+    );
+
+    pool_state.usdc_deposited = pool_state
+        .usdc_deposited
+        .checked_add(received)
+        .ok_or(VaultError::MathError)?;
+    pool_state.total_rewards_claimed = pool_state
+        .total_rewards_claimed
+        .checked_add(to_compound)
+        .ok_or(VaultError::MathError)?;
+    user_state.pending_rewards = user_state
+        .pending_rewards
+        .checked_sub(to_compound)
+        .ok_or(VaultError::MathError)?;
+
+    // 5) Mint LP tokens to the user
+    let cpi_ctx_mint = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        MintTo {
+            mint: ctx.accounts.lp_token_mint.to_account_info(),
+            to: ctx.accounts.user_lp_token_account.to_account_info(),
+            authority: pool_state.to_account_info(),
+        },
+    );
+    token_interface::mint_to(
+        cpi_ctx_mint.with_signer(&[&[b"pool-state".as_ref(), &[pool_state_bump]]]),
+        lp_to_mint,
+    )?;
+
+    user_state.lp_token_balance = user_state
+        .lp_token_balance
+        .checked_add(lp_to_mint)
+        .ok_or(VaultError::MathError)?;
+    update_boosted_balance(pool_state, user_state)?;
+
+    emit!(RewardsClaimed {
+        version: EVENT_SCHEMA_VERSION,
+        user: ctx.accounts.user.key(),
+        amount: to_compound,
+        timestamp: now as i64,
+        total_claimed: pool_state.total_rewards_claimed,
+    });
+
+    msg!(
+        "User {} compounded {} USDC into {} LP tokens.",
+        ctx.accounts.user.key(),
+        to_compound,
+        lp_to_mint
+    );
+    Ok(())
+}
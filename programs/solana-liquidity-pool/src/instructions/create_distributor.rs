@@ -0,0 +1,134 @@
+use crate::{
+    errors::VaultError, instructions::helpers::validate_mint_extensions, state::*,
+    DistributorCreated, EVENT_SCHEMA_VERSION,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+
+#[derive(Accounts)]
+#[instruction(campaign_id: u64, merkle_root: [u8; 32], total_amount: u64, num_claims: u64)]
+pub struct CreateDistributor<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"pool-state".as_ref()],
+        bump,
+        constraint = pool_state.admin == admin.key() @ VaultError::Unauthorized
+    )]
+    pub pool_state: Account<'info, PoolState>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + MerkleDistributor::LEN,
+        seeds = [b"merkle-distributor".as_ref(), campaign_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub distributor: Account<'info, MerkleDistributor>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + ClaimBitmap::space(num_claims),
+        seeds = [b"claim-bitmap".as_ref(), distributor.key().as_ref()],
+        bump
+    )]
+    pub claim_bitmap: Account<'info, ClaimBitmap>,
+
+    /// Mint of the token being distributed
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// Campaign vault, owned by the distributor PDA
+    #[account(
+        init,
+        payer = admin,
+        token::mint = mint,
+        token::authority = distributor,
+        seeds = [b"merkle-vault".as_ref(), distributor.key().as_ref()],
+        bump
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Admin's token account funding the campaign
+    #[account(
+        mut,
+        constraint = admin_token_account.mint == mint.key() @ VaultError::InvalidTokenMint
+    )]
+    pub admin_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+pub fn handle_create_distributor(
+    ctx: Context<CreateDistributor>,
+    campaign_id: u64,
+    merkle_root: [u8; 32],
+    total_amount: u64,
+    num_claims: u64,
+    expiry_time: u64,
+) -> Result<()> {
+    require!(
+        num_claims > 0 && num_claims <= MAX_MERKLE_CLAIMS,
+        VaultError::TooManyClaims
+    );
+    require!(total_amount > 0, VaultError::ZeroAmount);
+    let now = Clock::get()?.unix_timestamp;
+    require!(expiry_time > now as u64, VaultError::DistributorExpired);
+
+    validate_mint_extensions(&ctx.accounts.mint)?;
+
+    // Fund the campaign vault from the admin
+    let cpi_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        TransferChecked {
+            from: ctx.accounts.admin_token_account.to_account_info(),
+            mint: ctx.accounts.mint.to_account_info(),
+            to: ctx.accounts.vault.to_account_info(),
+            authority: ctx.accounts.admin.to_account_info(),
+        },
+    );
+    token_interface::transfer_checked(cpi_ctx, total_amount, ctx.accounts.mint.decimals)?;
+
+    // Transfer-fee mints deliver less than was sent; the campaign holds what arrived
+    ctx.accounts.vault.reload()?;
+    let funded_amount = ctx.accounts.vault.amount;
+
+    let distributor = &mut ctx.accounts.distributor;
+    distributor.campaign_id = campaign_id;
+    distributor.merkle_root = merkle_root;
+    distributor.mint = ctx.accounts.mint.key();
+    distributor.vault = ctx.accounts.vault.key();
+    distributor.total_amount = funded_amount;
+    distributor.total_claimed = 0;
+    distributor.num_claims = num_claims;
+    distributor.expiry_time = expiry_time;
+    distributor.clawed_back = false;
+    distributor.bump = ctx.bumps.distributor;
+
+    let claim_bitmap = &mut ctx.accounts.claim_bitmap;
+    claim_bitmap.distributor = distributor.key();
+    claim_bitmap.claimed = vec![0; num_claims.div_ceil(8) as usize];
+
+    msg!(
+        "Created merkle campaign {} with {} tokens for {} claims, expiring at {}.",
+        campaign_id,
+        funded_amount,
+        num_claims,
+        expiry_time
+    );
+
+    emit!(DistributorCreated {
+        version: EVENT_SCHEMA_VERSION,
+        campaign_id,
+        mint: ctx.accounts.mint.key(),
+        vault: ctx.accounts.vault.key(),
+        total_amount: funded_amount,
+        num_claims,
+        expiry_time,
+        timestamp: now,
+    });
+    Ok(())
+}
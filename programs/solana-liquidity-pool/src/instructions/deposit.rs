@@ -1,10 +1,12 @@
 use crate::{
-    errors::VaultError, instructions::helpers::*, state::*, CHAINLINK_PROGRAM_ID,
-    DEVNET_SOL_PRICE_FEED, MAINNET_SOL_PRICE_FEED,
+    chainlink_program_id, errors::VaultError, instructions::helpers::*, pyth_sol_price_feed,
+    sol_price_feed, state::*, Deposited, ReferralRegistered, EVENT_SCHEMA_VERSION,
 };
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Mint, MintTo, Token, TokenAccount, Transfer};
-use chainlink_solana as chainlink;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{
+    self, Mint, MintTo, TokenAccount, TokenInterface, TransferChecked,
+};
 
 /// Context for deposit
 #[derive(Accounts)]
@@ -26,7 +28,7 @@ pub struct Deposit<'info> {
         constraint = user_token_account.owner == user.key(),
         constraint = user_token_account.mint == vault_account.mint
     )]
-    pub user_token_account: Account<'info, TokenAccount>,
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
 
     /// Vault for either SOL (wrapped) or USDC
     #[account(
@@ -35,7 +37,14 @@ pub struct Deposit<'info> {
             vault_account.key() == pool_state.sol_vault || 
             vault_account.key() == pool_state.usdc_vault
     )]
-    pub vault_account: Account<'info, TokenAccount>,
+    pub vault_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Mint of `vault_account`
+    #[account(
+        constraint = vault_mint.key() == vault_account.mint @ VaultError::InvalidTokenMint,
+        mint::token_program = vault_token_program
+    )]
+    pub vault_mint: InterfaceAccount<'info, Mint>,
 
     /// The user's associated UserState
     #[account(
@@ -50,33 +59,43 @@ pub struct Deposit<'info> {
     /// LP token mint
     #[account(
         mut,
-        constraint = lp_token_mint.key() == pool_state.lp_token_mint
+        constraint = lp_token_mint.key() == pool_state.lp_token_mint,
+        mint::token_program = lp_token_program
     )]
-    pub lp_token_mint: Account<'info, Mint>,
+    pub lp_token_mint: InterfaceAccount<'info, Mint>,
 
-    /// The user's LP token account (where minted LP tokens will go)
+    /// The user's LP token account (where minted LP tokens will go), created on first deposit
     #[account(
-        mut,
-        constraint = user_lp_token_account.owner == user.key(),
-        constraint = user_lp_token_account.mint == lp_token_mint.key()
+        init_if_needed,
+        payer = user,
+        associated_token::mint = lp_token_mint,
+        associated_token::authority = user,
+        associated_token::token_program = lp_token_program
     )]
-    pub user_lp_token_account: Account<'info, TokenAccount>,
+    pub user_lp_token_account: InterfaceAccount<'info, TokenAccount>,
 
     /// CHECK: Validated in constraint
-    #[account(address = CHAINLINK_PROGRAM_ID.parse::<Pubkey>().unwrap())]
+    #[account(address = chainlink_program_id())]
     pub chainlink_program: AccountInfo<'info>,
 
     /// CHECK: Validated in constraint
-    #[account(
-        address = if cfg!(feature = "devnet") {
-            DEVNET_SOL_PRICE_FEED
-        } else {
-            MAINNET_SOL_PRICE_FEED
-        }.parse::<Pubkey>().unwrap()
-    )]
+    #[account(address = sol_price_feed())]
     pub chainlink_feed: AccountInfo<'info>,
 
-    pub token_program: Program<'info, Token>,
+    /// CHECK: Validated in constraint, parsed in `read_pyth_conf_bps`
+    #[account(address = pyth_sol_price_feed())]
+    pub pyth_price_feed: AccountInfo<'info>,
+
+    /// Optional referrer to register on the user's first deposit
+    #[account(mut)]
+    pub referrer_state: Option<Account<'info, ReferrerState>>,
+
+    /// Token program of the vault's mint
+    pub vault_token_program: Interface<'info, TokenInterface>,
+
+    /// Token program of the LP mint
+    pub lp_token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
 }
 
@@ -87,43 +106,71 @@ pub fn handle_deposit(ctx: Context<Deposit>, token_amount: u64) -> Result<()> {
     let pool_state = &mut ctx.accounts.pool_state;
     let user_state = &mut ctx.accounts.user_state;
 
-    // If depositing SOL, fetch latest price from Chainlink.
-    if ctx.accounts.vault_account.key() == pool_state.sol_vault {
-        msg!("Depositing SOL, fetching latest Chainlink price");
-        let round = chainlink::latest_round_data(
-            ctx.accounts.chainlink_program.to_account_info(),
-            ctx.accounts.chainlink_feed.to_account_info(),
-        )?;
-        // Update stored SOL price (8 decimals from Chainlink)
-        pool_state.sol_usd_price = round.answer;
-        msg!("Updated SOL/USD price to {} (8 dec)", round.answer);
-    }
+    // Always refresh the SOL/USD price: USDC deposits are priced against the SOL holdings too
+    let now = Clock::get()?.unix_timestamp;
+    let price = read_chainlink_price(
+        ctx.accounts.chainlink_program.to_account_info(),
+        ctx.accounts.chainlink_feed.to_account_info(),
+        now,
+    )?;
+    // Update stored SOL price (8 decimals from Chainlink)
+    record_sol_price(pool_state, price, now)?;
+    let conf_bps = read_pyth_conf_bps(&ctx.accounts.pyth_price_feed, now)?;
+    record_price_confidence(pool_state, conf_bps)?;
+    msg!("Updated SOL/USD price to {} (8 dec)", price);
 
     msg!("Transferring {} tokens to vault", token_amount);
+    let vault_before = ctx.accounts.vault_account.amount;
     // Transfer tokens from user into the vault
     let transfer_cpi_ctx = CpiContext::new(
-        ctx.accounts.token_program.to_account_info(),
-        Transfer {
+        ctx.accounts.vault_token_program.to_account_info(),
+        TransferChecked {
             from: ctx.accounts.user_token_account.to_account_info(),
+            mint: ctx.accounts.vault_mint.to_account_info(),
             to: ctx.accounts.vault_account.to_account_info(),
             authority: ctx.accounts.user.to_account_info(),
         },
     );
-    token::transfer(transfer_cpi_ctx, token_amount)?;
+    token_interface::transfer_checked(
+        transfer_cpi_ctx,
+        token_amount,
+        ctx.accounts.vault_mint.decimals,
+    )?;
     msg!("Token transfer successful");
 
+    // Credit only what the vault received, net of any Token-2022 transfer fee
+    ctx.accounts.vault_account.reload()?;
+    let received = ctx
+        .accounts
+        .vault_account
+        .amount
+        .checked_sub(vault_before)
+        .ok_or(VaultError::MathError)?;
+    msg!("Vault received {} tokens", received);
+
     // Now compute the *initial* AUM (in USD with 6 decimals) based on updated totals.
     msg!("Computing initial AUM");
-        
+
+    // Under the conservative policy the pool is valued at the higher of spot and EMA,
+    // and incoming SOL at the lower, so a price spike can't be used to mint cheap LP
+    let (low_price, high_price) = sol_price_range(pool_state);
+
     // 1) Convert total SOL to USD (6 decimals), 2) Add total USDC (6 decimals)
-    let total_sol_usd = get_sol_usd_value(pool_state.sol_deposited, pool_state.sol_usd_price)?;
-    msg!("Total SOL value in USD: {} (6 dec)", total_sol_usd);
-    
-    let initial_aum = total_sol_usd
-    .checked_add(pool_state.usdc_deposited)
-    .ok_or(VaultError::MathError)?;
+    let initial_aum = calculate_aum_at_price(pool_state, high_price)?;
     msg!("Initial total AUM: {} (6 dec)", initial_aum);
 
+    // Price the composition fee against the holdings before this deposit lands
+    let is_sol = ctx.accounts.vault_account.key() == pool_state.sol_vault;
+    let received_usd = if is_sol {
+        get_sol_usd_value(received, low_price)?
+    } else {
+        received
+    };
+    // The fee stays in the vault, accruing to existing LPs
+    let lp_supply = ctx.accounts.lp_token_mint.supply;
+    let (lp_to_mint, fee_usd) =
+        calculate_deposit_lp(pool_state, is_sol, received_usd, lp_supply, initial_aum)?;
+
     // Determine how many tokens in USD were deposited (6 decimals).
     // Also update the pool's recorded total (sol_deposited / usdc_deposited).
     let deposit_usd = if ctx.accounts.vault_account.key() == pool_state.sol_vault {
@@ -131,7 +178,7 @@ pub fn handle_deposit(ctx: Context<Deposit>, token_amount: u64) -> Result<()> {
         // Increase total SOL (9 decimals)
         pool_state.sol_deposited = pool_state
             .sol_deposited
-            .checked_add(token_amount)
+            .checked_add(received)
             .ok_or(VaultError::MathError)?;
         msg!(
             "Updated pool SOL balance to {} (9 dec)",
@@ -139,13 +186,13 @@ pub fn handle_deposit(ctx: Context<Deposit>, token_amount: u64) -> Result<()> {
         );
 
         // Convert SOL to USD (returns USD with 6 decimals)
-        get_sol_usd_value(token_amount, pool_state.sol_usd_price)?
+        get_sol_usd_value(received, low_price)?
     } else if ctx.accounts.vault_account.key() == pool_state.usdc_vault {
         msg!("Processing USDC deposit");
         // Increase total USDC (6 decimals)
         pool_state.usdc_deposited = pool_state
             .usdc_deposited
-            .checked_add(token_amount)
+            .checked_add(received)
             .ok_or(VaultError::MathError)?;
         msg!(
             "Updated pool USDC balance to {} (6 dec)",
@@ -153,68 +200,87 @@ pub fn handle_deposit(ctx: Context<Deposit>, token_amount: u64) -> Result<()> {
         );
 
         // USDC already has 6 decimals, matching our USD representation
-        token_amount
+        received
     } else {
         return err!(VaultError::InvalidTokenMint);
     };
     msg!("Deposit value in USD: {} (6 dec)", deposit_usd);
 
-    // Figure out how many LP tokens to mint:
-    msg!("Calculating LP tokens to mint");
-    //   if first deposit, deposit_usd tokens (6 decimals)
-    //   else deposit_usd * (LP supply / AUM) -> maintains 6 decimals due to ratio
-    let lp_supply = ctx.accounts.lp_token_mint.supply;
     msg!("Current LP token supply: {}", lp_supply);
-
-    let lp_to_mint = if lp_supply == 0 {
-        msg!("First deposit - LP tokens will match USD value");
-        // For first deposit, LP tokens match USD value (6 decimals)
-        deposit_usd
-    } else {
-        msg!("Calculating proportional LP tokens");
-        // For subsequent deposits:
-        // deposit_usd (6 dec) * lp_supply / initial_aum (6 dec) = result with 6 decimals
-        // initial_aum / lp_supply gives = lp_token_price
-        // lp_to_mint = deposit_usd / (initial_aum / lp_supply)
-        // so we can reverse the formula to: lp_to_mint = deposit_usd * (lp_supply / initial_aum)
-        deposit_usd
-            .checked_mul(lp_supply)
-            .ok_or(VaultError::MathError)?
-            .checked_div(initial_aum.max(1))
-            .ok_or(VaultError::MathError)?
-    };
     msg!("Will mint {} LP tokens (6 dec)", lp_to_mint);
 
     // Update user rewards, then mint LP
     msg!("Updating user rewards before minting");
-    update_rewards(pool_state, user_state, &ctx.accounts.lp_token_mint)?;
+    update_rewards(pool_state, user_state)?;
 
     // Mint LP tokens (which maintain 6 decimals like USD)
     msg!("Minting LP tokens to user");
     let cpi_ctx_mint = CpiContext::new(
-        ctx.accounts.token_program.to_account_info(),
+        ctx.accounts.lp_token_program.to_account_info(),
         MintTo {
             mint: ctx.accounts.lp_token_mint.to_account_info(),
             to: ctx.accounts.user_lp_token_account.to_account_info(),
             authority: pool_state.to_account_info(),
         },
     );
-    token::mint_to(
+    token_interface::mint_to(
         cpi_ctx_mint.with_signer(&[&[b"pool-state".as_ref(), &[ctx.bumps.pool_state]]]),
         lp_to_mint,
     )?;
 
+    // Attribute the user to a referrer on their first deposit
+    if let Some(referrer_state) = ctx.accounts.referrer_state.as_mut() {
+        if user_state.referrer == Pubkey::default() && user_state.lp_token_balance == 0 {
+            require_keys_neq!(
+                referrer_state.owner,
+                ctx.accounts.user.key(),
+                VaultError::SelfReferral
+            );
+
+            user_state.referrer = referrer_state.key();
+            referrer_state.referee_count = referrer_state
+                .referee_count
+                .checked_add(1)
+                .ok_or(VaultError::MathError)?;
+
+            emit!(ReferralRegistered {
+                version: EVENT_SCHEMA_VERSION,
+                user: ctx.accounts.user.key(),
+                referrer: referrer_state.owner,
+                code: referrer_state.code,
+                timestamp: Clock::get()?.unix_timestamp,
+            });
+            msg!("Registered referrer {}", referrer_state.owner);
+        }
+    }
+
     // Update user's record of how many LP tokens they hold (6 decimals)
     user_state.owner = ctx.accounts.user.key();
     user_state.lp_token_balance = user_state
         .lp_token_balance
         .checked_add(lp_to_mint)
         .ok_or(VaultError::MathError)?;
+    update_boosted_balance(pool_state, user_state)?;
     msg!(
         "Updated user's LP token balance to {} (6 dec)",
         user_state.lp_token_balance
     );
 
+    emit!(Deposited {
+        version: EVENT_SCHEMA_VERSION,
+        user: ctx.accounts.user.key(),
+        vault: ctx.accounts.vault_account.key(),
+        amount: received,
+        deposit_usd,
+        lp_minted: lp_to_mint,
+        nav_per_lp: calculate_nav_per_lp(initial_aum, lp_supply)?,
+        fee_usd,
+        sol_usd_price: pool_state.sol_usd_price,
+        aum_before: initial_aum,
+        aum_after: calculate_aum(pool_state)?,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
     msg!(
         "Deposit successful. Minted {} LP tokens (6 decimals).",
         lp_to_mint
@@ -0,0 +1,179 @@
+use crate::{
+    chainlink_program_id, errors::VaultError, instructions::helpers::*, pyth_sol_price_feed,
+    sol_price_feed, state::*, Deposited, EVENT_SCHEMA_VERSION,
+};
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Transfer as SystemTransfer};
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{self, Mint, MintTo, SyncNative, TokenAccount, TokenInterface};
+
+/// Context for depositing native SOL, wrapped directly into the SOL vault
+#[derive(Accounts)]
+pub struct DepositSol<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    /// Global PoolState
+    #[account(
+        mut,
+        seeds = [b"pool-state".as_ref()],
+        bump
+    )]
+    pub pool_state: Account<'info, PoolState>,
+
+    /// The pool's wrapped SOL vault
+    #[account(
+        mut,
+        constraint = sol_vault.key() == pool_state.sol_vault @ VaultError::InvalidTokenMint,
+        constraint = sol_vault.is_native() @ VaultError::InvalidTokenMint,
+        token::token_program = vault_token_program
+    )]
+    pub sol_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// The user's associated UserState
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + UserState::LEN,
+        seeds = [b"user-state".as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub user_state: Account<'info, UserState>,
+
+    /// LP token mint
+    #[account(
+        mut,
+        constraint = lp_token_mint.key() == pool_state.lp_token_mint,
+        mint::token_program = lp_token_program
+    )]
+    pub lp_token_mint: InterfaceAccount<'info, Mint>,
+
+    /// The user's LP token account (where minted LP tokens will go), created on first deposit
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = lp_token_mint,
+        associated_token::authority = user,
+        associated_token::token_program = lp_token_program
+    )]
+    pub user_lp_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: Validated in constraint
+    #[account(address = chainlink_program_id())]
+    pub chainlink_program: AccountInfo<'info>,
+
+    /// CHECK: Validated in constraint
+    #[account(address = sol_price_feed())]
+    pub chainlink_feed: AccountInfo<'info>,
+
+    /// CHECK: Validated in constraint, parsed in `read_pyth_conf_bps`
+    #[account(address = pyth_sol_price_feed())]
+    pub pyth_price_feed: AccountInfo<'info>,
+
+    /// Token program of the native SOL mint
+    pub vault_token_program: Interface<'info, TokenInterface>,
+
+    /// Token program of the LP mint
+    pub lp_token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_deposit_sol(ctx: Context<DepositSol>, lamports: u64) -> Result<()> {
+    require!(lamports > 0, VaultError::ZeroAmount);
+    msg!("Starting native deposit of {} lamports", lamports);
+
+    let pool_state = &mut ctx.accounts.pool_state;
+    let user_state = &mut ctx.accounts.user_state;
+
+    // Refresh the SOL/USD price before valuing the deposit
+    let now = Clock::get()?.unix_timestamp;
+    let price = read_chainlink_price(
+        ctx.accounts.chainlink_program.to_account_info(),
+        ctx.accounts.chainlink_feed.to_account_info(),
+        now,
+    )?;
+    record_sol_price(pool_state, price, now)?;
+    let conf_bps = read_pyth_conf_bps(&ctx.accounts.pyth_price_feed, now)?;
+    record_price_confidence(pool_state, conf_bps)?;
+    msg!("Updated SOL/USD price to {} (8 dec)", price);
+
+    // Move lamports straight into the wSOL vault and sync its token balance
+    system_program::transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            SystemTransfer {
+                from: ctx.accounts.user.to_account_info(),
+                to: ctx.accounts.sol_vault.to_account_info(),
+            },
+        ),
+        lamports,
+    )?;
+    token_interface::sync_native(CpiContext::new(
+        ctx.accounts.vault_token_program.to_account_info(),
+        SyncNative {
+            account: ctx.accounts.sol_vault.to_account_info(),
+        },
+    ))?;
+    msg!("Wrapped {} lamports into the SOL vault", lamports);
+
+    // Value the pool high and the incoming SOL low under the conservative policy
+    let (low_price, high_price) = sol_price_range(pool_state);
+    let initial_aum = calculate_aum_at_price(pool_state, high_price)?;
+    msg!("Initial total AUM: {} (6 dec)", initial_aum);
+
+    let deposit_usd = get_sol_usd_value(lamports, low_price)?;
+    msg!("Deposit value in USD: {} (6 dec)", deposit_usd);
+    let lp_supply = ctx.accounts.lp_token_mint.supply;
+    let (lp_to_mint, fee_usd) =
+        calculate_deposit_lp(pool_state, true, deposit_usd, lp_supply, initial_aum)?;
+
+    pool_state.sol_deposited = pool_state
+        .sol_deposited
+        .checked_add(lamports)
+        .ok_or(VaultError::MathError)?;
+    msg!("Will mint {} LP tokens (6 dec)", lp_to_mint);
+
+    update_rewards(pool_state, user_state)?;
+
+    let cpi_ctx_mint = CpiContext::new(
+        ctx.accounts.lp_token_program.to_account_info(),
+        MintTo {
+            mint: ctx.accounts.lp_token_mint.to_account_info(),
+            to: ctx.accounts.user_lp_token_account.to_account_info(),
+            authority: pool_state.to_account_info(),
+        },
+    );
+    token_interface::mint_to(
+        cpi_ctx_mint.with_signer(&[&[b"pool-state".as_ref(), &[ctx.bumps.pool_state]]]),
+        lp_to_mint,
+    )?;
+
+    user_state.owner = ctx.accounts.user.key();
+    user_state.lp_token_balance = user_state
+        .lp_token_balance
+        .checked_add(lp_to_mint)
+        .ok_or(VaultError::MathError)?;
+    update_boosted_balance(pool_state, user_state)?;
+
+    emit!(Deposited {
+        version: EVENT_SCHEMA_VERSION,
+        user: ctx.accounts.user.key(),
+        vault: ctx.accounts.sol_vault.key(),
+        amount: lamports,
+        deposit_usd,
+        lp_minted: lp_to_mint,
+        nav_per_lp: calculate_nav_per_lp(initial_aum, lp_supply)?,
+        fee_usd,
+        sol_usd_price: pool_state.sol_usd_price,
+        aum_before: initial_aum,
+        aum_after: calculate_aum(pool_state)?,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!(
+        "Native SOL deposit successful. Minted {} LP tokens (6 decimals).",
+        lp_to_mint
+    );
+    Ok(())
+}
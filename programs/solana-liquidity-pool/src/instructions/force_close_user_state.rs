@@ -1,6 +1,10 @@
 use anchor_lang::prelude::*;
 
-use crate::{errors::VaultError, state::PoolState};
+use crate::{
+    errors::VaultError,
+    state::{PoolState, UserState},
+    UserStateForceClosed, EVENT_SCHEMA_VERSION,
+};
 
 #[derive(Accounts)]
 pub struct ForceCloseUserState<'info> {
@@ -36,6 +40,16 @@ pub fn handle_force_close_user_state(ctx: Context<ForceCloseUserState>) -> Resul
         VaultError::Unauthorized
     );
 
+    // If the account still deserializes, remove its weight from the reward denominator
+    if let Ok(user_state) =
+        UserState::try_deserialize(&mut &ctx.accounts.user_state.try_borrow_data()?[..])
+    {
+        let pool_state = &mut ctx.accounts.pool_state;
+        pool_state.total_boosted_supply = pool_state
+            .total_boosted_supply
+            .saturating_sub(user_state.boosted_balance);
+    }
+
     // Transfer lamports back to admin
     let dest_starting_lamports = ctx.accounts.admin.lamports();
     **ctx.accounts.admin.lamports.borrow_mut() = dest_starting_lamports
@@ -44,9 +58,16 @@ pub fn handle_force_close_user_state(ctx: Context<ForceCloseUserState>) -> Resul
     **ctx.accounts.user_state.lamports.borrow_mut() = 0;
 
     // Clear the account data
-    ctx.accounts.user_state.assign(&ctx.program_id);
+    ctx.accounts.user_state.assign(ctx.program_id);
     ctx.accounts.user_state.realloc(0, false)?;
 
+    emit!(UserStateForceClosed {
+        version: EVENT_SCHEMA_VERSION,
+        user: ctx.accounts.target_user.key(),
+        admin: ctx.accounts.admin.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
     msg!(
         "Force closed user state account for: {}",
         ctx.accounts.target_user.key()
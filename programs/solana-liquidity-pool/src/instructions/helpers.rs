@@ -1,60 +1,1046 @@
 use crate::{
     errors::VaultError,
-    state::{PoolState, UserState},
+    state::{
+        get_sol_usd_value, PoolState, UserState, BPS_DENOMINATOR, MAX_LOCK_BOOST_BPS,
+        MAX_LOCK_WEEKS, MAX_ORACLE_AGE, PRICE_POLICY_SPOT, WEEK_SECONDS,
+    },
 };
 use anchor_lang::prelude::*;
-use anchor_spl::token::Mint;
+use anchor_lang::solana_program::keccak;
+use anchor_spl::token_interface::spl_token_2022::{
+    self,
+    extension::{BaseStateWithExtensions, ExtensionType, StateWithExtensions},
+};
+use anchor_spl::token_interface::Mint;
+#[cfg(not(feature = "localnet"))]
+use chainlink_solana as chainlink;
+
+/// Fixed-point scale of `cumulative_reward_per_token`
+pub const PRECISION: u128 = 1_000_000_000_000_000_000;
+
+// Pyth v2 price account layout, unused on localnet where the mock oracle stands in
+#[cfg(not(feature = "localnet"))]
+const PYTH_MAGIC: u32 = 0xa1b2c3d4;
+#[cfg(not(feature = "localnet"))]
+const PYTH_VERSION: u32 = 2;
+#[cfg(not(feature = "localnet"))]
+const PYTH_ACCOUNT_TYPE_PRICE: u32 = 3;
+#[cfg(not(feature = "localnet"))]
+const PYTH_STATUS_TRADING: u32 = 1;
+#[cfg(not(feature = "localnet"))]
+const PYTH_TIMESTAMP_OFFSET: usize = 96;
+#[cfg(not(feature = "localnet"))]
+const PYTH_AGG_PRICE_OFFSET: usize = 208;
+#[cfg(not(feature = "localnet"))]
+const PYTH_AGG_CONF_OFFSET: usize = 216;
+#[cfg(not(feature = "localnet"))]
+const PYTH_AGG_STATUS_OFFSET: usize = 224;
+#[cfg(not(feature = "localnet"))]
+const PYTH_PRICE_ACCOUNT_MIN_LEN: usize = 240;
 
-pub fn update_rewards(
+pub fn update_rewards(pool_state: &mut PoolState, user_state: &mut UserState) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp as u64;
+    update_rewards_at(pool_state, user_state, now)
+}
+
+/// `update_rewards` at an explicit timestamp
+pub fn update_rewards_at(
     pool_state: &mut PoolState,
     user_state: &mut UserState,
-    lp_token_mint: &Account<Mint>,
+    now: u64,
 ) -> Result<()> {
-    const PRECISION: u128 = 1_000_000_000_000;
+    accrue_pool_rewards(pool_state, now)?;
 
-    let now = Clock::get()?.unix_timestamp as u64;
+    // Rewards are shared by boosted balance, so locked LP earns its multiplier.
+    // Flooring both index products (rather than their difference) makes repeated
+    // settlements telescope, so a user loses at most one unit per balance change.
+    let earned_total = rewards_at_index(
+        user_state.boosted_balance,
+        pool_state.cumulative_reward_per_token,
+    )?;
+    let earned_before = rewards_at_index(
+        user_state.boosted_balance,
+        user_state.previous_cumulated_reward_per_token,
+    )?;
+    let user_reward = earned_total.saturating_sub(earned_before);
+    let user_reward = u64::try_from(user_reward).map_err(|_| VaultError::MathError)?;
+
+    // A lock only boosts until `lock_end_time`. For a lock that expired since the last
+    // settlement, the index growth after expiry is paid at 1x and the boost on it is
+    // forfeited to the other stakers. The index at expiry isn't known, so the growth is
+    // split by time.
+    let lock_expired = user_state.locked_lp_amount > 0
+        && user_state.lock_multiplier_bps > BPS_DENOMINATOR
+        && now >= user_state.lock_end_time;
+    let forfeited_boost = if lock_expired {
+        expired_boost_rewards(user_state, user_reward, now)?
+    } else {
+        0
+    };
+    let user_reward = user_reward - forfeited_boost;
+
+    // Referred users pass a share of what they earn on to their referrer
+    let referral_fee = if user_state.referrer != Pubkey::default() {
+        (user_reward as u128)
+            .checked_mul(pool_state.referral_share_bps as u128)
+            .ok_or(VaultError::MathError)?
+            .checked_div(BPS_DENOMINATOR as u128)
+            .ok_or(VaultError::MathError)? as u64
+    } else {
+        0
+    };
+
+    user_state.pending_rewards = user_state
+        .pending_rewards
+        .checked_add(user_reward - referral_fee)
+        .ok_or(VaultError::MathError)?;
+    user_state.referral_rewards_owed = user_state
+        .referral_rewards_owed
+        .checked_add(referral_fee)
+        .ok_or(VaultError::MathError)?;
+
+    user_state.previous_cumulated_reward_per_token = pool_state.cumulative_reward_per_token;
+    user_state.last_reward_update = now;
+
+    // An expired lock stops boosting once the rewards it earned are settled. The LP stays
+    // in escrow until `unlock_lp` returns it without a penalty
+    if lock_expired {
+        msg!(
+            "LP lock expired, {} LP now earning at 1x",
+            user_state.locked_lp_amount
+        );
+        user_state.lock_multiplier_bps = BPS_DENOMINATOR;
+        update_boosted_balance(pool_state, user_state)?;
+        redistribute_rewards(pool_state, user_state, forfeited_boost)?;
+    }
+
+    Ok(())
+}
+
+/// Part of `user_reward` that came from the lock boost after `lock_end_time`.
+/// `user_reward` was earned at the boosted weight since the later of the last settlement
+/// and the lock start.
+fn expired_boost_rewards(user_state: &UserState, user_reward: u64, now: u64) -> Result<u64> {
+    let since = user_state
+        .last_reward_update
+        .max(user_state.lock_start_time);
+    let expired_from = user_state.lock_end_time.max(since);
+    if user_reward == 0 || now <= expired_from || user_state.boosted_balance == 0 {
+        return Ok(0);
+    }
+
+    // Earned at the boosted weight after expiry, then the part above 1x
+    let after_expiry = (user_reward as u128)
+        .checked_mul((now - expired_from) as u128)
+        .ok_or(VaultError::MathError)?
+        / (now - since) as u128;
+    let boost = user_state
+        .boosted_balance
+        .saturating_sub(user_state.lp_token_balance) as u128;
+    let forfeited = after_expiry
+        .checked_mul(boost)
+        .ok_or(VaultError::MathError)?
+        / user_state.boosted_balance as u128;
+
+    Ok(forfeited as u64)
+}
+
+/// `floor(balance * index / PRECISION)`, split so a large historical index can't overflow
+fn rewards_at_index(balance: u64, index: u128) -> Result<u128> {
+    let balance = balance as u128;
+    let whole = balance
+        .checked_mul(index / PRECISION)
+        .ok_or(VaultError::MathError)?;
+    let fraction = balance
+        .checked_mul(index % PRECISION)
+        .ok_or(VaultError::MathError)?
+        / PRECISION;
+
+    whole
+        .checked_add(fraction)
+        .ok_or_else(|| error!(VaultError::MathError))
+}
+
+/// Emit the current period's rewards up to `now` into the reward index.
+/// Emission is linear in time and computed from the period total, so the full
+/// funded amount is emitted by `reward_end_time` regardless of call frequency.
+pub fn accrue_pool_rewards(pool_state: &mut PoolState, now: u64) -> Result<()> {
+    let emitted = rewards_emitted_until(pool_state, now)?;
+    let newly_emitted = emitted.saturating_sub(pool_state.reward_period_emitted);
+    pool_state.reward_period_emitted = pool_state.reward_period_emitted.max(emitted);
+    pool_state.last_distribution_time = now;
 
-    if lp_token_mint.supply == 0 {
-        pool_state.last_distribution_time = now;
+    if newly_emitted == 0 {
         return Ok(());
     }
 
-    let time_diff = now.saturating_sub(pool_state.last_distribution_time);
-    if time_diff > 0 {
-        let pending_rewards = (pool_state.tokens_per_interval as u128)
-            .checked_mul(time_diff as u128)
+    // With nobody staked the emission has no recipient yet, so it joins the next period
+    if pool_state.total_boosted_supply == 0 {
+        pool_state.reward_carryover = pool_state
+            .reward_carryover
+            .checked_add(newly_emitted)
             .ok_or(VaultError::MathError)?;
+        return Ok(());
+    }
 
-        let reward_per_token = pending_rewards
-            .checked_mul(PRECISION)
-            .ok_or(VaultError::MathError)?
-            .checked_div(lp_token_mint.supply as u128)
+    add_to_reward_index(pool_state, newly_emitted, pool_state.total_boosted_supply)
+}
+
+/// Amount of the current period's rewards emitted by `now`
+fn rewards_emitted_until(pool_state: &PoolState, now: u64) -> Result<u64> {
+    if pool_state.reward_end_time <= pool_state.reward_start_time {
+        return Ok(pool_state.reward_period_emitted);
+    }
+
+    let duration = pool_state.reward_end_time - pool_state.reward_start_time;
+    let elapsed = now
+        .min(pool_state.reward_end_time)
+        .saturating_sub(pool_state.reward_start_time);
+    let emitted = (pool_state.reward_period_amount as u128)
+        .checked_mul(elapsed as u128)
+        .ok_or(VaultError::MathError)?
+        / duration as u128;
+
+    Ok(emitted as u64)
+}
+
+/// Spread `amount` over `supply` in the reward index, carrying the division remainder
+/// into the next call so no dust is lost.
+fn add_to_reward_index(pool_state: &mut PoolState, amount: u64, supply: u64) -> Result<()> {
+    let numerator = (amount as u128)
+        .checked_mul(PRECISION)
+        .ok_or(VaultError::MathError)?
+        .checked_add(pool_state.reward_index_remainder)
+        .ok_or(VaultError::MathError)?;
+    let supply = supply as u128;
+
+    pool_state.cumulative_reward_per_token = pool_state
+        .cumulative_reward_per_token
+        .checked_add(numerator / supply)
+        .ok_or(VaultError::MathError)?;
+    pool_state.reward_index_remainder = numerator % supply;
+
+    Ok(())
+}
+
+/// Start a new `WEEK_SECONDS` reward period funded with `usdc_amount` at `now`.
+/// Whatever the previous period had not emitted yet rolls into the new one.
+pub fn begin_reward_period(pool_state: &mut PoolState, usdc_amount: u64, now: u64) -> Result<()> {
+    accrue_pool_rewards(pool_state, now)?;
+
+    let unemitted = pool_state
+        .reward_period_amount
+        .saturating_sub(pool_state.reward_period_emitted);
+
+    pool_state.reward_period_amount = usdc_amount
+        .checked_add(unemitted)
+        .and_then(|amount| amount.checked_add(pool_state.reward_carryover))
+        .ok_or(VaultError::MathError)?;
+    pool_state.reward_period_emitted = 0;
+    pool_state.reward_carryover = 0;
+    pool_state.total_rewards_deposited = pool_state
+        .total_rewards_deposited
+        .checked_add(usdc_amount)
+        .ok_or(VaultError::MathError)?;
+
+    // Informational per-second rate; emission itself uses the exact period total
+    pool_state.tokens_per_interval = pool_state.reward_period_amount / WEEK_SECONDS;
+    pool_state.reward_start_time = now;
+    pool_state.reward_end_time = now.checked_add(WEEK_SECONDS).ok_or(VaultError::MathError)?;
+    pool_state.last_distribution_time = now;
+
+    Ok(())
+}
+
+/// Reward multiplier (in bps) for locking LP for `lock_weeks` weeks.
+/// Grows linearly from 1x up to `MAX_LOCK_BOOST_BPS` for a maximum-length lock.
+pub fn lock_multiplier_bps(lock_weeks: u64) -> Result<u64> {
+    let bonus = (MAX_LOCK_BOOST_BPS - BPS_DENOMINATOR)
+        .checked_mul(lock_weeks.min(MAX_LOCK_WEEKS))
+        .ok_or(VaultError::MathError)?
+        .checked_div(MAX_LOCK_WEEKS)
+        .ok_or(VaultError::MathError)?;
+
+    Ok(BPS_DENOMINATOR + bonus)
+}
+
+/// Recompute the user's boosted balance and apply the difference to the pool-wide boosted supply.
+/// Must be called after `update_rewards` whenever the user's LP balance or lock changes.
+pub fn update_boosted_balance(
+    pool_state: &mut PoolState,
+    user_state: &mut UserState,
+) -> Result<()> {
+    let unlocked = user_state
+        .lp_token_balance
+        .checked_sub(user_state.locked_lp_amount)
+        .ok_or(VaultError::MathError)?;
+
+    let boosted_locked = (user_state.locked_lp_amount as u128)
+        .checked_mul(user_state.lock_multiplier_bps as u128)
+        .ok_or(VaultError::MathError)?
+        .checked_div(BPS_DENOMINATOR as u128)
+        .ok_or(VaultError::MathError)?;
+
+    let boosted_balance = (unlocked as u128)
+        .checked_add(boosted_locked)
+        .ok_or(VaultError::MathError)?;
+    let boosted_balance = u64::try_from(boosted_balance).map_err(|_| VaultError::MathError)?;
+
+    pool_state.total_boosted_supply = pool_state
+        .total_boosted_supply
+        .checked_sub(user_state.boosted_balance)
+        .ok_or(VaultError::MathError)?
+        .checked_add(boosted_balance)
+        .ok_or(VaultError::MathError)?;
+
+    user_state.boosted_balance = boosted_balance;
+
+    Ok(())
+}
+
+/// Share `amount` of already-funded rewards among every staker except `user_state`
+/// by bumping the reward index, or carry it into the next period if nobody else is staked.
+/// The user must have been settled with `update_rewards` first.
+pub fn redistribute_rewards(
+    pool_state: &mut PoolState,
+    user_state: &mut UserState,
+    amount: u64,
+) -> Result<()> {
+    let other_supply = pool_state
+        .total_boosted_supply
+        .saturating_sub(user_state.boosted_balance);
+    if amount == 0 {
+        return Ok(());
+    }
+
+    // With no other stakers the forfeit waits for the next reward period
+    if other_supply == 0 {
+        pool_state.reward_carryover = pool_state
+            .reward_carryover
+            .checked_add(amount)
             .ok_or(VaultError::MathError)?;
+        return Ok(());
+    }
+
+    add_to_reward_index(pool_state, amount, other_supply)?;
+
+    // Skip the user past the bump so they don't earn back their own forfeit
+    user_state.previous_cumulated_reward_per_token = pool_state.cumulative_reward_per_token;
+
+    Ok(())
+}
 
-        pool_state.cumulative_reward_per_token = pool_state
-            .cumulative_reward_per_token
-            .checked_add(reward_per_token)
+/// Pool AUM in USD (6 decimals): total SOL at the stored SOL/USD price plus total USDC.
+pub fn calculate_aum(pool_state: &PoolState) -> Result<u64> {
+    calculate_aum_at_price(pool_state, pool_state.sol_usd_price)
+}
+
+/// AUM in USD (6 decimals) with the SOL holdings valued at `sol_usd_price`
+pub fn calculate_aum_at_price(pool_state: &PoolState, sol_usd_price: i128) -> Result<u64> {
+    let total_sol_usd = get_sol_usd_value(pool_state.sol_deposited, sol_usd_price)?;
+    msg!("Total SOL value in USD: {} (6 dec)", total_sol_usd);
+
+    let aum = total_sol_usd
+        .checked_add(pool_state.usdc_deposited)
+        .ok_or(VaultError::MathError)?;
+
+    Ok(aum)
+}
+
+/// Latest SOL/USD answer from Chainlink (8 decimals), rejecting non-positive answers
+/// and rounds older than `MAX_ORACLE_AGE`.
+pub fn read_chainlink_price<'info>(
+    chainlink_program: AccountInfo<'info>,
+    chainlink_feed: AccountInfo<'info>,
+    now: i64,
+) -> Result<i128> {
+    let (answer, timestamp) = latest_chainlink_answer(chainlink_program, chainlink_feed)?;
+    require!(answer > 0, VaultError::InvalidPriceFeed);
+    require!(
+        now.saturating_sub(timestamp) <= MAX_ORACLE_AGE,
+        VaultError::StalePrice
+    );
+    Ok(answer)
+}
+
+#[cfg(not(feature = "localnet"))]
+fn latest_chainlink_answer<'info>(
+    chainlink_program: AccountInfo<'info>,
+    chainlink_feed: AccountInfo<'info>,
+) -> Result<(i128, i64)> {
+    let round = chainlink::latest_round_data(chainlink_program, chainlink_feed)?;
+    Ok((round.answer, round.timestamp as i64))
+}
+
+#[cfg(feature = "localnet")]
+fn latest_chainlink_answer<'info>(
+    _chainlink_program: AccountInfo<'info>,
+    chainlink_feed: AccountInfo<'info>,
+) -> Result<(i128, i64)> {
+    let feed = load_mock_feed(&chainlink_feed)?;
+    Ok((feed.price, feed.timestamp))
+}
+
+/// Store a fresh SOL/USD round as the spot price and fold it into the EMA, an
+/// exponential moving average that moves `elapsed / (elapsed + window)` of the way to
+/// the new price. The weight stays below one, so a single round after an idle gap only
+/// moves it part of the way (halfway after a full window) rather than jumping to spot.
+pub fn record_sol_price(pool_state: &mut PoolState, price: i128, now: i64) -> Result<()> {
+    pool_state.sol_usd_price = price;
+
+    let elapsed = now.saturating_sub(pool_state.ema_last_update).max(0) as i128;
+    let window = pool_state.ema_window as i128;
+    if pool_state.sol_usd_ema <= 0 || window == 0 {
+        pool_state.sol_usd_ema = price;
+    } else {
+        let step = price
+            .checked_sub(pool_state.sol_usd_ema)
+            .and_then(|diff| diff.checked_mul(elapsed))
+            .ok_or(VaultError::MathError)?
+            / (elapsed + window);
+        pool_state.sol_usd_ema = pool_state
+            .sol_usd_ema
+            .checked_add(step)
             .ok_or(VaultError::MathError)?;
+    }
+    pool_state.ema_last_update = now;
+    Ok(())
+}
+
+/// (low, high) SOL/USD prices under the pool's price policy, widened by the oracle
+/// confidence interval. Mints value the pool at `high` and incoming SOL at `low`;
+/// burns the other way around.
+pub fn sol_price_range(pool_state: &PoolState) -> (i128, i128) {
+    let spot = pool_state.sol_usd_price;
+    let ema = pool_state.sol_usd_ema;
+    let (low, high) = if pool_state.price_policy == PRICE_POLICY_SPOT || ema <= 0 {
+        (spot, spot)
+    } else {
+        (spot.min(ema), spot.max(ema))
+    };
+
+    let conf_bps = pool_state.sol_usd_conf_bps.min(BPS_DENOMINATOR) as i128;
+    let denominator = BPS_DENOMINATOR as i128;
+    (
+        low * (denominator - conf_bps) / denominator,
+        high * (denominator + conf_bps) / denominator,
+    )
+}
+
+/// Confidence interval of a Pyth price account as a share of its price, rounded up.
+/// Rejects accounts that are malformed, not trading or older than `MAX_ORACLE_AGE`.
+pub fn read_pyth_conf_bps(price_account: &AccountInfo, now: i64) -> Result<u64> {
+    let (price, conf, timestamp) = latest_pyth_price(price_account)?;
+    require!(
+        now.saturating_sub(timestamp) <= MAX_ORACLE_AGE,
+        VaultError::StalePrice
+    );
+    require!(price > 0, VaultError::InvalidPriceFeed);
+
+    let conf_bps = (conf as u128)
+        .checked_mul(BPS_DENOMINATOR as u128)
+        .ok_or(VaultError::MathError)?
+        .div_ceil(price as u128);
+    Ok(conf_bps.min(u64::MAX as u128) as u64)
+}
+
+/// Aggregate (price, conf, publish time) of a trading Pyth price account
+#[cfg(not(feature = "localnet"))]
+fn latest_pyth_price(price_account: &AccountInfo) -> Result<(i128, u64, i64)> {
+    let data = price_account.try_borrow_data()?;
+    require!(
+        data.len() >= PYTH_PRICE_ACCOUNT_MIN_LEN,
+        VaultError::InvalidPriceFeed
+    );
+    let read_u32 = |offset: usize| u32::from_le_bytes(data[offset..offset + 4].try_into().unwrap());
+    let read_u64 = |offset: usize| u64::from_le_bytes(data[offset..offset + 8].try_into().unwrap());
+
+    require!(
+        read_u32(0) == PYTH_MAGIC
+            && read_u32(4) == PYTH_VERSION
+            && read_u32(8) == PYTH_ACCOUNT_TYPE_PRICE,
+        VaultError::InvalidPriceFeed
+    );
+    require!(
+        read_u32(PYTH_AGG_STATUS_OFFSET) == PYTH_STATUS_TRADING,
+        VaultError::StalePrice
+    );
+
+    Ok((
+        read_u64(PYTH_AGG_PRICE_OFFSET) as i64 as i128,
+        read_u64(PYTH_AGG_CONF_OFFSET),
+        read_u64(PYTH_TIMESTAMP_OFFSET) as i64,
+    ))
+}
+
+#[cfg(feature = "localnet")]
+fn latest_pyth_price(price_account: &AccountInfo) -> Result<(i128, u64, i64)> {
+    let feed = load_mock_feed(price_account)?;
+    Ok((feed.price, feed.conf, feed.timestamp))
+}
+
+/// The mock oracle's feed, standing in for both Chainlink and Pyth on localnet
+#[cfg(feature = "localnet")]
+fn load_mock_feed(feed: &AccountInfo) -> Result<mock_oracle::PriceFeed> {
+    require_keys_eq!(*feed.owner, mock_oracle::ID, VaultError::InvalidPriceFeed);
+    let data = feed.try_borrow_data()?;
+    mock_oracle::PriceFeed::try_deserialize(&mut &data[..])
+}
 
-        pool_state.last_distribution_time = now;
+/// Store the latest confidence interval, rejecting it when wider than `max_conf_bps`
+pub fn record_price_confidence(pool_state: &mut PoolState, conf_bps: u64) -> Result<()> {
+    require!(
+        pool_state.max_conf_bps == 0 || conf_bps <= pool_state.max_conf_bps,
+        VaultError::PriceConfidenceTooWide
+    );
+    pool_state.sol_usd_conf_bps = conf_bps;
+    Ok(())
+}
+
+/// USD value of one LP token (6 decimals); 1 USD before the first deposit
+pub fn calculate_nav_per_lp(aum: u64, lp_supply: u64) -> Result<u64> {
+    if lp_supply == 0 {
+        return Ok(1_000_000);
     }
 
-    let user_reward = (user_state.lp_token_balance as u128)
-        .checked_mul(
-            pool_state
-                .cumulative_reward_per_token
-                .saturating_sub(user_state.previous_cumulated_reward_per_token),
-        )
+    let nav_per_lp = (aum as u128)
+        .checked_mul(1_000_000)
         .ok_or(VaultError::MathError)?
-        .checked_div(PRECISION)
+        .checked_div(lp_supply as u128)
         .ok_or(VaultError::MathError)?;
 
-    user_state.pending_rewards = user_state
-        .pending_rewards
-        .checked_add(user_reward as u64)
+    u64::try_from(nav_per_lp).map_err(|_| error!(VaultError::MathError))
+}
+
+/// LP tokens (6 decimals) to mint for a deposit worth `deposit_usd` into a pool worth `aum`
+/// before the deposit.
+pub fn calculate_lp_to_mint(deposit_usd: u64, lp_supply: u64, aum: u64) -> Result<u64> {
+    if lp_supply == 0 {
+        msg!("First deposit - LP tokens will match USD value");
+        // For first deposit, LP tokens match USD value (6 decimals)
+        return Ok(deposit_usd);
+    }
+
+    msg!("Calculating proportional LP tokens");
+    // For subsequent deposits:
+    // deposit_usd (6 dec) * lp_supply / aum (6 dec) = result with 6 decimals
+    // aum / lp_supply gives = lp_token_price
+    // lp_to_mint = deposit_usd / (aum / lp_supply)
+    // so we can reverse the formula to: lp_to_mint = deposit_usd * (lp_supply / aum)
+    let lp_to_mint = (deposit_usd as u128)
+        .checked_mul(lp_supply as u128)
+        .ok_or(VaultError::MathError)?
+        .checked_div(aum.max(1) as u128)
         .ok_or(VaultError::MathError)?;
 
-    user_state.previous_cumulated_reward_per_token = pool_state.cumulative_reward_per_token;
+    u64::try_from(lp_to_mint).map_err(|_| error!(VaultError::MathError))
+}
+
+/// Fee (in bps) for moving `usd_delta` of SOL (`is_sol`) or USDC into (`increment`) or out
+/// of the pool. Moves toward the target weight get the tax rebated off the base fee,
+/// moves away from it pay the base fee plus a tax scaled by the resulting imbalance.
+pub fn calculate_composition_fee_bps(
+    pool_state: &PoolState,
+    is_sol: bool,
+    usd_delta: u64,
+    increment: bool,
+) -> Result<u64> {
+    if pool_state.base_fee_bps == 0 && pool_state.tax_fee_bps == 0 {
+        return Ok(0);
+    }
+
+    let aum = calculate_aum(pool_state)? as u128;
+    let (initial_amount, weight_bps) = if is_sol {
+        (
+            get_sol_usd_value(pool_state.sol_deposited, pool_state.sol_usd_price)? as u128,
+            pool_state.sol_target_weight_bps,
+        )
+    } else {
+        (
+            pool_state.usdc_deposited as u128,
+            BPS_DENOMINATOR.saturating_sub(pool_state.sol_target_weight_bps),
+        )
+    };
+
+    let target_amount = aum
+        .checked_mul(weight_bps as u128)
+        .ok_or(VaultError::MathError)?
+        / BPS_DENOMINATOR as u128;
+    if target_amount == 0 {
+        return Ok(pool_state.base_fee_bps);
+    }
+
+    let next_amount = if increment {
+        initial_amount
+            .checked_add(usd_delta as u128)
+            .ok_or(VaultError::MathError)?
+    } else {
+        initial_amount.saturating_sub(usd_delta as u128)
+    };
+    let initial_diff = initial_amount.abs_diff(target_amount);
+    let next_diff = next_amount.abs_diff(target_amount);
+    let tax_fee_bps = pool_state.tax_fee_bps as u128;
+
+    // Moving toward the target: rebate the tax off the base fee
+    if next_diff < initial_diff {
+        let rebate_bps = tax_fee_bps
+            .checked_mul(initial_diff)
+            .ok_or(VaultError::MathError)?
+            / target_amount;
+        return Ok(pool_state.base_fee_bps.saturating_sub(rebate_bps as u64));
+    }
+
+    // Moving away from the target: tax by the average imbalance, capped at the target
+    let average_diff = ((initial_diff + next_diff) / 2).min(target_amount);
+    let tax_bps = tax_fee_bps
+        .checked_mul(average_diff)
+        .ok_or(VaultError::MathError)?
+        / target_amount;
+    pool_state
+        .base_fee_bps
+        .checked_add(tax_bps as u64)
+        .ok_or_else(|| error!(VaultError::MathError))
+}
+
+/// SOL share of AUM in bps at the stored SOL/USD price
+pub fn sol_weight_bps(pool_state: &PoolState) -> Result<u64> {
+    let sol_usd = get_sol_usd_value(pool_state.sol_deposited, pool_state.sol_usd_price)?;
+    let aum = calculate_aum(pool_state)?;
+    let weight = (sol_usd as u128)
+        .checked_mul(BPS_DENOMINATOR as u128)
+        .ok_or(VaultError::MathError)?
+        / aum.max(1) as u128;
+
+    Ok(weight as u64)
+}
+
+/// Reject a swap that leaves the SOL weight outside the swap band unless it moved
+/// the pool closer to the band, so an out-of-band pool can always be swapped back
+pub fn check_composition_limits(pool_state: &PoolState, weight_before_bps: u64) -> Result<()> {
+    let distance = |weight_bps: u64| {
+        pool_state
+            .min_sol_weight_bps
+            .saturating_sub(weight_bps)
+            .max(weight_bps.saturating_sub(pool_state.max_sol_weight_bps))
+    };
+    let distance_after = distance(sol_weight_bps(pool_state)?);
+    require!(
+        distance_after == 0 || distance_after < distance(weight_before_bps),
+        VaultError::CompositionLimitExceeded
+    );
 
     Ok(())
 }
+
+/// Portion of `usd_amount` charged at `fee_bps`
+pub fn calculate_fee(usd_amount: u64, fee_bps: u64) -> Result<u64> {
+    let fee = (usd_amount as u128)
+        .checked_mul(fee_bps as u128)
+        .ok_or(VaultError::MathError)?
+        / BPS_DENOMINATOR as u128;
+
+    u64::try_from(fee).map_err(|_| error!(VaultError::MathError))
+}
+
+/// LP to mint and composition fee in USD for a `deposit_usd` deposit of SOL (`is_sol`) or
+/// USDC, priced against the holdings and `aum` before it lands. Every path that mints LP
+/// for new assets goes through here so none of them skips the fee.
+pub fn calculate_deposit_lp(
+    pool_state: &PoolState,
+    is_sol: bool,
+    deposit_usd: u64,
+    lp_supply: u64,
+    aum: u64,
+) -> Result<(u64, u64)> {
+    let fee_bps = calculate_composition_fee_bps(pool_state, is_sol, deposit_usd, true)?;
+    msg!("Composition fee: {} bps", fee_bps);
+    let fee_usd = calculate_fee(deposit_usd, fee_bps)?;
+    let lp_to_mint = calculate_lp_to_mint(deposit_usd - fee_usd, lp_supply, aum)?;
+
+    Ok((lp_to_mint, fee_usd))
+}
+
+/// Pro-rata share of a vault balance owed for `lp_amount` LP tokens: balance * lp_amount / lp_supply
+pub fn calculate_proportional_share(balance: u64, lp_amount: u64, lp_supply: u64) -> Result<u64> {
+    let share = (balance as u128)
+        .checked_mul(lp_amount as u128)
+        .ok_or(VaultError::MathError)?
+        .checked_div(lp_supply.max(1) as u128)
+        .ok_or(VaultError::MathError)?;
+
+    u64::try_from(share).map_err(|_| error!(VaultError::MathError))
+}
+
+/// Token-2022 mint extensions the pool can hold. Transfer fees are handled by crediting
+/// the vault's actual balance change; anything else (hooks, permanent delegates,
+/// interest, confidential transfers, ...) would break the pool's accounting.
+const SUPPORTED_MINT_EXTENSIONS: [ExtensionType; 3] = [
+    ExtensionType::TransferFeeConfig,
+    ExtensionType::MetadataPointer,
+    ExtensionType::TokenMetadata,
+];
+
+/// Reject Token-2022 mints carrying extensions outside `SUPPORTED_MINT_EXTENSIONS`
+pub fn validate_mint_extensions(mint: &InterfaceAccount<Mint>) -> Result<()> {
+    let mint_info = mint.to_account_info();
+    if *mint_info.owner != spl_token_2022::ID {
+        return Ok(());
+    }
+
+    let data = mint_info.try_borrow_data()?;
+    let state = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&data)?;
+    for extension in state.get_extension_types()? {
+        if !SUPPORTED_MINT_EXTENSIONS.contains(&extension) {
+            msg!("Unsupported mint extension: {:?}", extension);
+            return err!(VaultError::UnsupportedMintExtension);
+        }
+    }
+    Ok(())
+}
+
+/// USD value (6 decimals) of `lp_amount` LP tokens: lp_amount * aum / lp_supply
+pub fn calculate_withdrawal_usd(lp_amount: u64, lp_supply: u64, aum: u64) -> Result<u64> {
+    let withdrawal_usd = (lp_amount as u128)
+        .checked_mul(aum as u128)
+        .ok_or(VaultError::MathError)?
+        .checked_div(lp_supply.max(1) as u128)
+        .ok_or(VaultError::MathError)?;
+
+    u64::try_from(withdrawal_usd).map_err(|_| error!(VaultError::MathError))
+}
+
+/// Merkle leaf for a distributor claim: keccak256(index || claimant || amount)
+pub fn merkle_leaf(index: u64, claimant: &Pubkey, amount: u64) -> [u8; 32] {
+    keccak::hashv(&[
+        &index.to_le_bytes(),
+        claimant.as_ref(),
+        &amount.to_le_bytes(),
+    ])
+    .0
+}
+
+/// Verify `leaf` against `root`, hashing each pair in sorted order
+pub fn verify_merkle_proof(proof: &[[u8; 32]], root: [u8; 32], leaf: [u8; 32]) -> bool {
+    let computed = proof.iter().fold(leaf, |node, sibling| {
+        if node <= *sibling {
+            keccak::hashv(&[&node, sibling]).0
+        } else {
+            keccak::hashv(&[sibling, &node]).0
+        }
+    });
+
+    computed == root
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const START: u64 = 1_700_000_000;
+
+    fn staked_user(pool_state: &mut PoolState, lp_token_balance: u64) -> UserState {
+        let mut user_state = UserState {
+            lp_token_balance,
+            ..Default::default()
+        };
+        update_boosted_balance(pool_state, &mut user_state).unwrap();
+        user_state
+    }
+
+    /// Irregular timestamps covering the whole period and a bit past its end
+    fn timestamps() -> Vec<u64> {
+        let mut now = START;
+        let mut step = 1;
+        let mut timestamps = Vec::new();
+        while now < START + WEEK_SECONDS + 100 {
+            step = step * 31 % 1_999 + 1;
+            now += step;
+            timestamps.push(now);
+        }
+        timestamps
+    }
+
+    #[test]
+    fn emits_full_funded_amount_to_single_staker() {
+        let mut pool_state = PoolState::default();
+        let mut user_state = staked_user(&mut pool_state, 123_456_789);
+
+        // Not a multiple of the period length, so a floored per-second rate would lose dust
+        let funded = 1_000_000_007;
+        begin_reward_period(&mut pool_state, funded, START).unwrap();
+
+        for now in timestamps() {
+            update_rewards_at(&mut pool_state, &mut user_state, now).unwrap();
+        }
+
+        assert_eq!(pool_state.reward_period_emitted, funded);
+        assert!(user_state.pending_rewards <= funded);
+        assert!(funded - user_state.pending_rewards <= 1);
+    }
+
+    #[test]
+    fn splits_full_funded_amount_between_stakers() {
+        let mut pool_state = PoolState::default();
+        let mut alice = staked_user(&mut pool_state, 1_000_003);
+        let mut bob = staked_user(&mut pool_state, 2_999_999);
+
+        let funded = 604_799;
+        begin_reward_period(&mut pool_state, funded, START).unwrap();
+
+        for (i, now) in timestamps().into_iter().enumerate() {
+            if i % 3 == 0 {
+                update_rewards_at(&mut pool_state, &mut alice, now).unwrap();
+            } else {
+                update_rewards_at(&mut pool_state, &mut bob, now).unwrap();
+            }
+        }
+        let end = START + WEEK_SECONDS + 200;
+        update_rewards_at(&mut pool_state, &mut alice, end).unwrap();
+        update_rewards_at(&mut pool_state, &mut bob, end).unwrap();
+
+        let distributed = alice.pending_rewards + bob.pending_rewards;
+        assert_eq!(pool_state.reward_period_emitted, funded);
+        assert!(distributed <= funded);
+        assert!(funded - distributed <= 2); // at most one unit per staker
+    }
+
+    #[test]
+    fn referral_share_is_split_from_referee_rewards() {
+        let mut pool_state = PoolState {
+            referral_share_bps: 1_000,
+            ..Default::default()
+        };
+        let mut user_state = staked_user(&mut pool_state, 1_000_000);
+        user_state.referrer = Pubkey::new_unique();
+
+        let funded = 1_000_000;
+        begin_reward_period(&mut pool_state, funded, START).unwrap();
+        update_rewards_at(&mut pool_state, &mut user_state, START + WEEK_SECONDS).unwrap();
+
+        assert_eq!(user_state.referral_rewards_owed, 100_000);
+        assert_eq!(user_state.pending_rewards, 900_000);
+    }
+
+    #[test]
+    fn new_period_rolls_over_unemitted_rewards() {
+        let mut pool_state = PoolState::default();
+        let mut user_state = staked_user(&mut pool_state, 5_000_000);
+
+        begin_reward_period(&mut pool_state, 1_000_001, START).unwrap();
+        update_rewards_at(&mut pool_state, &mut user_state, START + WEEK_SECONDS / 2).unwrap();
+
+        let restart = START + WEEK_SECONDS / 2 + 1;
+        begin_reward_period(&mut pool_state, 333_333, restart).unwrap();
+        update_rewards_at(&mut pool_state, &mut user_state, restart + WEEK_SECONDS).unwrap();
+
+        let funded = 1_000_001 + 333_333;
+        assert_eq!(pool_state.total_rewards_deposited, funded);
+        assert!(user_state.pending_rewards <= funded);
+        assert!(funded - user_state.pending_rewards <= 1);
+    }
+
+    #[test]
+    fn emission_during_zero_supply_gap_joins_next_period() {
+        let mut pool_state = PoolState::default();
+        let mut user_state = staked_user(&mut pool_state, 1_000_000);
+
+        let funded = 1_000_000;
+        begin_reward_period(&mut pool_state, funded, START).unwrap();
+        update_rewards_at(&mut pool_state, &mut user_state, START + WEEK_SECONDS / 4).unwrap();
+
+        // Everyone leaves for the middle half of the period, then restakes
+        user_state.lp_token_balance = 0;
+        update_boosted_balance(&mut pool_state, &mut user_state).unwrap();
+        update_rewards_at(
+            &mut pool_state,
+            &mut user_state,
+            START + WEEK_SECONDS * 3 / 4,
+        )
+        .unwrap();
+        user_state.lp_token_balance = 1_000_000;
+        update_boosted_balance(&mut pool_state, &mut user_state).unwrap();
+
+        let end = START + WEEK_SECONDS;
+        update_rewards_at(&mut pool_state, &mut user_state, end).unwrap();
+        assert!(pool_state.reward_carryover.abs_diff(funded / 2) <= 1);
+
+        begin_reward_period(&mut pool_state, 0, end).unwrap();
+        update_rewards_at(&mut pool_state, &mut user_state, end + WEEK_SECONDS).unwrap();
+
+        assert_eq!(pool_state.reward_carryover, 0);
+        assert!(user_state.pending_rewards <= funded);
+        assert!(funded - user_state.pending_rewards <= 2);
+    }
+
+    #[test]
+    fn lock_boost_stops_at_lock_end() {
+        let mut pool_state = PoolState::default();
+        let mut locker = staked_user(&mut pool_state, 1_000_000);
+        let mut other = staked_user(&mut pool_state, 1_000_000);
+
+        // A 2x lock that ends halfway through the period, settled only after the period
+        locker.locked_lp_amount = 1_000_000;
+        locker.lock_multiplier_bps = 20_000;
+        locker.lock_start_time = START;
+        locker.lock_end_time = START + WEEK_SECONDS / 2;
+        locker.last_reward_update = START;
+        update_boosted_balance(&mut pool_state, &mut locker).unwrap();
+
+        let funded = 3_000_000;
+        begin_reward_period(&mut pool_state, funded, START).unwrap();
+        let end = START + WEEK_SECONDS;
+        update_rewards_at(&mut pool_state, &mut locker, end).unwrap();
+        update_rewards_at(&mut pool_state, &mut other, end).unwrap();
+
+        // The locker's boost stayed in the supply until it was settled, so after lock end
+        // it earns 1/3 of the emission and the boost on top goes to the other staker
+        assert_eq!(locker.boosted_balance, 1_000_000);
+        assert!(locker.pending_rewards.abs_diff(1_500_000) <= 1);
+        assert!(other.pending_rewards.abs_diff(1_500_000) <= 1);
+    }
+
+    #[test]
+    fn sole_staker_forfeit_carries_into_next_period() {
+        let mut pool_state = PoolState::default();
+        let mut user_state = staked_user(&mut pool_state, 1_000_000);
+
+        begin_reward_period(&mut pool_state, 1_000_000, START).unwrap();
+        let end = START + WEEK_SECONDS;
+        update_rewards_at(&mut pool_state, &mut user_state, end).unwrap();
+        redistribute_rewards(&mut pool_state, &mut user_state, 250_000).unwrap();
+        user_state.pending_rewards -= 250_000;
+        assert_eq!(pool_state.reward_carryover, 250_000);
+
+        begin_reward_period(&mut pool_state, 0, end).unwrap();
+        update_rewards_at(&mut pool_state, &mut user_state, end + WEEK_SECONDS).unwrap();
+
+        assert_eq!(pool_state.reward_carryover, 0);
+        assert!(user_state.pending_rewards.abs_diff(1_000_000) <= 1);
+    }
+
+    #[test]
+    fn composition_fee_taxes_imbalance_and_rebates_rebalancing() {
+        // $3,000 of SOL and $1,000 of USDC against a 50/50 target
+        let pool_state = PoolState {
+            sol_deposited: 30_000_000_000,
+            usdc_deposited: 1_000_000_000,
+            sol_usd_price: 10_000_000_000,
+            sol_target_weight_bps: 5_000,
+            base_fee_bps: 30,
+            tax_fee_bps: 50,
+            ..Default::default()
+        };
+
+        let more_sol = calculate_composition_fee_bps(&pool_state, true, 500_000_000, true).unwrap();
+        let more_usdc =
+            calculate_composition_fee_bps(&pool_state, false, 500_000_000, true).unwrap();
+        let less_sol =
+            calculate_composition_fee_bps(&pool_state, true, 500_000_000, false).unwrap();
+
+        assert!(more_sol > pool_state.base_fee_bps);
+        assert_eq!(more_usdc, 5);
+        assert_eq!(less_sol, 5);
+    }
+
+    #[test]
+    fn compounding_pays_the_same_composition_fee_as_a_usdc_deposit() {
+        // $1,000 of SOL and $3,000 of USDC: more USDC moves away from the 50/50 target
+        let pool_state = PoolState {
+            sol_deposited: 10_000_000_000,
+            usdc_deposited: 3_000_000_000,
+            sol_usd_price: 10_000_000_000,
+            sol_target_weight_bps: 5_000,
+            base_fee_bps: 30,
+            tax_fee_bps: 50,
+            ..Default::default()
+        };
+        let lp_supply = 4_000_000_000;
+        let aum = calculate_aum(&pool_state).unwrap();
+        let rewards = 100_000_000;
+
+        // A USDC deposit of the same amount, priced step by step
+        let fee_bps = calculate_composition_fee_bps(&pool_state, false, rewards, true).unwrap();
+        let deposit_fee = calculate_fee(rewards, fee_bps).unwrap();
+        let deposit_lp = calculate_lp_to_mint(rewards - deposit_fee, lp_supply, aum).unwrap();
+
+        let (compound_lp, compound_fee) =
+            calculate_deposit_lp(&pool_state, false, rewards, lp_supply, aum).unwrap();
+
+        assert!(deposit_fee > 0);
+        assert_eq!((compound_lp, compound_fee), (deposit_lp, deposit_fee));
+        assert!(compound_lp < calculate_lp_to_mint(rewards, lp_supply, aum).unwrap());
+    }
+
+    #[test]
+    fn composition_limits_allow_swaps_back_toward_the_band() {
+        // $3,000 of SOL and $1,000 of USDC, above a 40-60% band
+        let mut pool_state = PoolState {
+            sol_deposited: 30_000_000_000,
+            usdc_deposited: 1_000_000_000,
+            sol_usd_price: 10_000_000_000,
+            min_sol_weight_bps: 4_000,
+            max_sol_weight_bps: 6_000,
+            ..Default::default()
+        };
+        let weight_before = sol_weight_bps(&pool_state).unwrap();
+        assert_eq!(weight_before, 7_500);
+
+        // Selling $500 of SOL is still out of band but closer to it
+        pool_state.sol_deposited -= 5_000_000_000;
+        pool_state.usdc_deposited += 500_000_000;
+        assert!(check_composition_limits(&pool_state, weight_before).is_ok());
+
+        // Buying SOL moves it further out
+        let weight_before = sol_weight_bps(&pool_state).unwrap();
+        pool_state.sol_deposited += 1_000_000_000;
+        pool_state.usdc_deposited -= 100_000_000;
+        assert!(check_composition_limits(&pool_state, weight_before).is_err());
+    }
+
+    #[test]
+    fn ema_lags_spot_spikes_and_bounds_mint_and_burn_prices() {
+        let mut pool_state = PoolState {
+            ema_window: 3_600,
+            ..Default::default()
+        };
+        let now = START as i64;
+        record_sol_price(&mut pool_state, 10_000_000_000, now).unwrap();
+        assert_eq!(pool_state.sol_usd_ema, 10_000_000_000);
+
+        // A spike 6 minutes later moves the EMA 360 / (360 + 3,600) = 1/11 of the way
+        record_sol_price(&mut pool_state, 20_000_000_000, now + 360).unwrap();
+        assert_eq!(pool_state.sol_usd_ema, 10_909_090_909);
+        assert_eq!(
+            sol_price_range(&pool_state),
+            (10_909_090_909, 20_000_000_000)
+        );
+
+        pool_state.price_policy = PRICE_POLICY_SPOT;
+        assert_eq!(
+            sol_price_range(&pool_state),
+            (20_000_000_000, 20_000_000_000)
+        );
+
+        // A round after a full idle window only closes half the gap instead of jumping to spot
+        record_sol_price(&mut pool_state, 20_000_000_000, now + 360 + 3_600).unwrap();
+        assert_eq!(pool_state.sol_usd_ema, 15_454_545_454);
+    }
+
+    #[test]
+    fn confidence_widens_the_price_range_and_is_capped() {
+        let mut pool_state = PoolState {
+            sol_usd_price: 10_000_000_000,
+            max_conf_bps: 100,
+            ..Default::default()
+        };
+
+        record_price_confidence(&mut pool_state, 50).unwrap();
+        assert_eq!(
+            sol_price_range(&pool_state),
+            (9_950_000_000, 10_050_000_000)
+        );
+
+        assert!(record_price_confidence(&mut pool_state, 101).is_err());
+        assert_eq!(pool_state.sol_usd_conf_bps, 50);
+    }
+}
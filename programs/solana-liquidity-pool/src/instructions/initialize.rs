@@ -1,6 +1,10 @@
-use crate::state::*;
+use crate::{
+    errors::VaultError, instructions::helpers::validate_mint_extensions, state::*, PoolInitialized,
+    EVENT_SCHEMA_VERSION,
+};
 use anchor_lang::prelude::*;
-use anchor_spl::token::{Mint, Token, TokenAccount};
+use anchor_spl::token::Token;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
 /// Context for initialize
 #[derive(Accounts)]
@@ -18,18 +22,51 @@ pub struct Initialize<'info> {
     )]
     pub pool_state: Account<'info, PoolState>,
 
-    /// SOL vault account (if using wrapped SOL, this would be a token account)
-    /// Here, assume you've already created the vault outside or are about to
-    #[account(mut)]
-    pub sol_vault: Account<'info, TokenAccount>,
+    /// Wrapped SOL mint
+    #[account(
+        address = anchor_spl::token::spl_token::native_mint::ID @ VaultError::InvalidTokenMint
+    )]
+    pub sol_mint: InterfaceAccount<'info, Mint>,
 
-    /// USDC vault account
-    #[account(mut)]
-    pub usdc_vault: Account<'info, TokenAccount>,
+    /// USDC mint (legacy SPL Token or Token-2022)
+    #[account(mint::token_program = token_program)]
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
 
-    /// Reward vault for USDC
-    #[account(mut)]
-    pub usdc_reward_vault: Account<'info, TokenAccount>,
+    /// Wrapped SOL vault, a PDA token account owned by the pool
+    #[account(
+        init,
+        payer = admin,
+        seeds = [b"vault".as_ref(), pool_state.key().as_ref(), sol_mint.key().as_ref()],
+        bump,
+        token::mint = sol_mint,
+        token::authority = pool_state,
+        token::token_program = sol_token_program
+    )]
+    pub sol_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// USDC vault, a PDA token account owned by the pool
+    #[account(
+        init,
+        payer = admin,
+        seeds = [b"vault".as_ref(), pool_state.key().as_ref(), usdc_mint.key().as_ref()],
+        bump,
+        token::mint = usdc_mint,
+        token::authority = pool_state,
+        token::token_program = token_program
+    )]
+    pub usdc_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Reward vault for USDC, a PDA token account owned by the pool
+    #[account(
+        init,
+        payer = admin,
+        seeds = [b"reward-vault".as_ref(), pool_state.key().as_ref(), usdc_mint.key().as_ref()],
+        bump,
+        token::mint = usdc_mint,
+        token::authority = pool_state,
+        token::token_program = token_program
+    )]
+    pub usdc_reward_vault: InterfaceAccount<'info, TokenAccount>,
 
     /// LP token mint
     #[account(
@@ -39,20 +76,28 @@ pub struct Initialize<'info> {
         mint::authority = pool_state,
         mint::freeze_authority = pool_state
     )]
-    pub lp_token_mint: Account<'info, Mint>,
+    pub lp_token_mint: InterfaceAccount<'info, Mint>,
+
+    /// Token program of the USDC and LP mints
+    pub token_program: Interface<'info, TokenInterface>,
 
-    #[account(address = anchor_spl::token::ID)]
-    pub token_program: Program<'info, Token>,
+    /// Legacy token program owning the native SOL mint
+    pub sol_token_program: Program<'info, Token>,
 
     pub system_program: Program<'info, System>,
     pub rent: Sysvar<'info, Rent>,
 }
 
 pub fn handle_initialize(ctx: Context<Initialize>) -> Result<()> {
+    validate_mint_extensions(&ctx.accounts.usdc_mint)?;
+    validate_mint_extensions(&ctx.accounts.lp_token_mint)?;
+
     let pool_state = &mut ctx.accounts.pool_state;
     pool_state.admin = ctx.accounts.admin.key();
     pool_state.sol_vault = ctx.accounts.sol_vault.key();
     pool_state.usdc_vault = ctx.accounts.usdc_vault.key();
+    pool_state.sol_mint = ctx.accounts.sol_mint.key();
+    pool_state.usdc_mint = ctx.accounts.usdc_mint.key();
     pool_state.lp_token_mint = ctx.accounts.lp_token_mint.key();
     pool_state.sol_deposited = 0;
     pool_state.usdc_deposited = 0;
@@ -60,6 +105,18 @@ pub fn handle_initialize(ctx: Context<Initialize>) -> Result<()> {
     pool_state.reward_start_time = 0;
     pool_state.reward_end_time = 0;
     pool_state.usdc_reward_vault = ctx.accounts.usdc_reward_vault.key();
+    pool_state.ema_window = DEFAULT_EMA_WINDOW;
+    pool_state.max_conf_bps = DEFAULT_MAX_CONF_BPS;
+
+    emit!(PoolInitialized {
+        version: EVENT_SCHEMA_VERSION,
+        admin: pool_state.admin,
+        lp_token_mint: pool_state.lp_token_mint,
+        sol_vault: pool_state.sol_vault,
+        usdc_vault: pool_state.usdc_vault,
+        usdc_reward_vault: pool_state.usdc_reward_vault,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
 
     msg!("Pool initialized successfully.");
     Ok(())
@@ -0,0 +1,36 @@
+use crate::{errors::VaultError, state::*};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct InitializeNavHistory<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"pool-state".as_ref()],
+        bump,
+        constraint = pool_state.admin == admin.key() @ VaultError::Unauthorized
+    )]
+    pub pool_state: Account<'info, PoolState>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + NavHistory::LEN,
+        seeds = [b"nav-history".as_ref()],
+        bump
+    )]
+    pub nav_history: AccountLoader<'info, NavHistory>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_initialize_nav_history(ctx: Context<InitializeNavHistory>) -> Result<()> {
+    ctx.accounts.nav_history.load_init()?;
+
+    msg!(
+        "NAV history initialized with room for {} snapshots.",
+        NAV_HISTORY_CAPACITY
+    );
+    Ok(())
+}
@@ -1,4 +1,4 @@
-use crate::state::*;
+use crate::{state::*, UserInitialized, EVENT_SCHEMA_VERSION};
 use anchor_lang::prelude::*;
 
 #[derive(Accounts)]
@@ -19,10 +19,11 @@ pub struct InitializeUser<'info> {
 }
 
 pub fn handle_initialize_user(ctx: Context<InitializeUser>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
     let user_state = &mut ctx.accounts.user_state;
     user_state.owner = ctx.accounts.user.key();
     user_state.lp_token_balance = 0;
-    user_state.last_claim_timestamp = Clock::get()?.unix_timestamp as u64;
+    user_state.last_claim_timestamp = now as u64;
     user_state.pending_rewards = 0;
     user_state.previous_cumulated_reward_per_token = 0;
 
@@ -30,5 +31,11 @@ pub fn handle_initialize_user(ctx: Context<InitializeUser>) -> Result<()> {
         "User state initialized successfully for: {}",
         user_state.owner
     );
+
+    emit!(UserInitialized {
+        version: EVENT_SCHEMA_VERSION,
+        user: user_state.owner,
+        timestamp: now,
+    });
     Ok(())
 }
@@ -0,0 +1,141 @@
+use crate::{
+    errors::VaultError, instructions::helpers::*, state::*, LpLocked, EVENT_SCHEMA_VERSION,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+
+#[derive(Accounts)]
+pub struct LockLp<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"pool-state".as_ref()],
+        bump
+    )]
+    pub pool_state: Account<'info, PoolState>,
+
+    #[account(
+        mut,
+        seeds = [b"user-state".as_ref(), user.key().as_ref()],
+        bump,
+        constraint = user_state.owner == user.key() @ VaultError::InvalidOwner
+    )]
+    pub user_state: Account<'info, UserState>,
+
+    /// LP token mint
+    #[account(
+        constraint = lp_token_mint.key() == pool_state.lp_token_mint @ VaultError::InvalidTokenMint,
+        mint::token_program = token_program
+    )]
+    pub lp_token_mint: InterfaceAccount<'info, Mint>,
+
+    /// User's LP token account the locked LP is taken from
+    #[account(
+        mut,
+        constraint = user_lp_token_account.owner == user.key(),
+        constraint = user_lp_token_account.mint == lp_token_mint.key()
+    )]
+    pub user_lp_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Pool-owned escrow holding the user's locked LP until `unlock_lp`
+    #[account(
+        init_if_needed,
+        payer = user,
+        seeds = [b"lp-escrow".as_ref(), user.key().as_ref()],
+        bump,
+        token::mint = lp_token_mint,
+        token::authority = pool_state,
+        token::token_program = token_program
+    )]
+    pub lp_escrow: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_lock_lp(ctx: Context<LockLp>, lp_token_amount: u64, lock_weeks: u64) -> Result<()> {
+    require!(
+        (MIN_LOCK_WEEKS..=MAX_LOCK_WEEKS).contains(&lock_weeks),
+        VaultError::InvalidLockDuration
+    );
+
+    let pool_state = &mut ctx.accounts.pool_state;
+    let user_state = &mut ctx.accounts.user_state;
+
+    // Settle rewards at the old weight (this also releases an expired lock)
+    update_rewards(pool_state, user_state)?;
+
+    require!(
+        lp_token_amount > 0 || user_state.locked_lp_amount > 0,
+        VaultError::ZeroAmount
+    );
+
+    let now = Clock::get()?.unix_timestamp as u64;
+    let lock_end_time = lock_weeks
+        .checked_mul(WEEK_SECONDS)
+        .and_then(|duration| now.checked_add(duration))
+        .ok_or(VaultError::MathError)?;
+
+    if user_state.locked_lp_amount > 0 {
+        require!(
+            lock_end_time >= user_state.lock_end_time,
+            VaultError::LockCannotBeShortened
+        );
+    }
+
+    let locked_lp_amount = user_state
+        .locked_lp_amount
+        .checked_add(lp_token_amount)
+        .ok_or(VaultError::MathError)?;
+    require!(
+        locked_lp_amount <= user_state.lp_token_balance,
+        VaultError::InsufficientLpBalance
+    );
+
+    // Locked LP is held by the pool so it can't leave the wallet before the lock ends
+    if lp_token_amount > 0 {
+        token_interface::transfer_checked(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.user_lp_token_account.to_account_info(),
+                    mint: ctx.accounts.lp_token_mint.to_account_info(),
+                    to: ctx.accounts.lp_escrow.to_account_info(),
+                    authority: ctx.accounts.user.to_account_info(),
+                },
+            ),
+            lp_token_amount,
+            ctx.accounts.lp_token_mint.decimals,
+        )?;
+    }
+
+    // The whole lock is re-based on the new duration
+    user_state.locked_lp_amount = locked_lp_amount;
+    user_state.lock_start_time = now;
+    user_state.lock_end_time = lock_end_time;
+    user_state.lock_multiplier_bps = lock_multiplier_bps(lock_weeks)?;
+    update_boosted_balance(pool_state, user_state)?;
+
+    msg!(
+        "Locked {} LP tokens for {} weeks at {} bps. Boosted balance: {}",
+        user_state.locked_lp_amount,
+        lock_weeks,
+        user_state.lock_multiplier_bps,
+        user_state.boosted_balance
+    );
+
+    emit!(LpLocked {
+        version: EVENT_SCHEMA_VERSION,
+        user: ctx.accounts.user.key(),
+        amount: lp_token_amount,
+        locked_lp_amount,
+        lock_weeks,
+        lock_end_time,
+        multiplier_bps: user_state.lock_multiplier_bps,
+        boosted_balance: user_state.boosted_balance,
+        timestamp: now as i64,
+    });
+    Ok(())
+}
@@ -1,25 +1,87 @@
 pub mod admin_deposit;
 pub mod admin_withdraw;
+pub mod checkpoint_users;
+pub mod claim_merkle_rewards;
+pub mod claim_referral_rewards;
 pub mod claim_rewards;
+pub mod claim_rewards_for;
+pub mod claim_rewards_vested;
+pub mod clawback_distributor;
 pub mod close_pool;
 pub mod close_user_state;
+pub mod compound_rewards;
+pub mod create_distributor;
 pub mod deposit;
+pub mod deposit_sol;
 pub mod force_close_user_state;
 pub mod helpers;
 pub mod initialize;
+pub mod initialize_nav_history;
 pub mod initialize_user;
+pub mod lock_lp;
+pub mod refresh_price;
+pub mod register_referral_code;
+pub mod set_claim_delegate;
+pub mod set_fee_config;
+pub mod set_lp_metadata;
+pub mod set_price_policy;
+pub mod set_referral_share;
+pub mod set_swap_config;
+pub mod set_treasury;
+pub mod set_vesting_config;
+pub mod snapshot;
 pub mod start_rewards;
+pub mod swap;
+pub mod sweep_rewards;
+pub mod unlock_lp;
+pub mod view_pending_rewards;
+pub mod view_pool_apr;
 pub mod withdraw;
+pub mod withdraw_proportional;
+pub mod withdraw_sol;
+pub mod withdraw_vested;
+pub mod zap_deposit;
 
 pub use admin_deposit::*;
 pub use admin_withdraw::*;
+pub use checkpoint_users::*;
+pub use claim_merkle_rewards::*;
+pub use claim_referral_rewards::*;
 pub use claim_rewards::*;
+pub use claim_rewards_for::*;
+pub use claim_rewards_vested::*;
+pub use clawback_distributor::*;
 pub use close_pool::*;
 pub use close_user_state::*;
+pub use compound_rewards::*;
+pub use create_distributor::*;
 pub use deposit::*;
+pub use deposit_sol::*;
 pub use force_close_user_state::*;
 pub use helpers::*;
 pub use initialize::*;
+pub use initialize_nav_history::*;
 pub use initialize_user::*;
+pub use lock_lp::*;
+pub use refresh_price::*;
+pub use register_referral_code::*;
+pub use set_claim_delegate::*;
+pub use set_fee_config::*;
+pub use set_lp_metadata::*;
+pub use set_price_policy::*;
+pub use set_referral_share::*;
+pub use set_swap_config::*;
+pub use set_treasury::*;
+pub use set_vesting_config::*;
+pub use snapshot::*;
 pub use start_rewards::*;
+pub use swap::*;
+pub use sweep_rewards::*;
+pub use unlock_lp::*;
+pub use view_pending_rewards::*;
+pub use view_pool_apr::*;
 pub use withdraw::*;
+pub use withdraw_proportional::*;
+pub use withdraw_sol::*;
+pub use withdraw_vested::*;
+pub use zap_deposit::*;
@@ -0,0 +1,50 @@
+use crate::{
+    chainlink_program_id, instructions::helpers::*, pyth_sol_price_feed, sol_price_feed, state::*,
+};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct RefreshPrice<'info> {
+    #[account(
+        mut,
+        seeds = [b"pool-state".as_ref()],
+        bump
+    )]
+    pub pool_state: Account<'info, PoolState>,
+
+    /// CHECK: Validated in constraint
+    #[account(address = chainlink_program_id())]
+    pub chainlink_program: AccountInfo<'info>,
+
+    /// CHECK: Validated in constraint
+    #[account(address = sol_price_feed())]
+    pub chainlink_feed: AccountInfo<'info>,
+
+    /// CHECK: Validated in constraint, parsed in `read_pyth_conf_bps`
+    #[account(address = pyth_sol_price_feed())]
+    pub pyth_price_feed: AccountInfo<'info>,
+}
+
+/// Permissionless keeper crank storing a fresh SOL/USD price and confidence interval,
+/// and folding the price into the EMA between price-touching instructions.
+pub fn handle_refresh_price(ctx: Context<RefreshPrice>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let price = read_chainlink_price(
+        ctx.accounts.chainlink_program.to_account_info(),
+        ctx.accounts.chainlink_feed.to_account_info(),
+        now,
+    )?;
+    let conf_bps = read_pyth_conf_bps(&ctx.accounts.pyth_price_feed, now)?;
+
+    let pool_state = &mut ctx.accounts.pool_state;
+    record_sol_price(pool_state, price, now)?;
+    record_price_confidence(pool_state, conf_bps)?;
+
+    msg!(
+        "SOL/USD spot {}, EMA {} (8 dec), confidence {} bps",
+        pool_state.sol_usd_price,
+        pool_state.sol_usd_ema,
+        pool_state.sol_usd_conf_bps
+    );
+    Ok(())
+}
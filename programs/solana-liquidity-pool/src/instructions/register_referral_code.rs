@@ -0,0 +1,44 @@
+use crate::{errors::VaultError, state::*, ReferralCodeRegistered, EVENT_SCHEMA_VERSION};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(code: [u8; 8])]
+pub struct RegisterReferralCode<'info> {
+    #[account(mut)]
+    pub referrer: Signer<'info>,
+
+    #[account(
+        init,
+        payer = referrer,
+        space = 8 + ReferrerState::LEN,
+        seeds = [b"referrer".as_ref(), code.as_ref()],
+        bump
+    )]
+    pub referrer_state: Account<'info, ReferrerState>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_register_referral_code(
+    ctx: Context<RegisterReferralCode>,
+    code: [u8; 8],
+) -> Result<()> {
+    require!(code != [0; 8], VaultError::InvalidReferralCode);
+
+    let referrer_state = &mut ctx.accounts.referrer_state;
+    referrer_state.owner = ctx.accounts.referrer.key();
+    referrer_state.code = code;
+    referrer_state.referee_count = 0;
+    referrer_state.pending_rewards = 0;
+    referrer_state.total_claimed = 0;
+
+    emit!(ReferralCodeRegistered {
+        version: EVENT_SCHEMA_VERSION,
+        referrer: referrer_state.owner,
+        code,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Referral code registered for {}", referrer_state.owner);
+    Ok(())
+}
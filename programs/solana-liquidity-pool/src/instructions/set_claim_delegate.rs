@@ -0,0 +1,42 @@
+use crate::{errors::VaultError, state::*, ClaimDelegateSet, EVENT_SCHEMA_VERSION};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetClaimDelegate<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"user-state".as_ref(), user.key().as_ref()],
+        bump,
+        constraint = user_state.owner == user.key() @ VaultError::InvalidOwner
+    )]
+    pub user_state: Account<'info, UserState>,
+}
+
+pub fn handle_set_claim_delegate(
+    ctx: Context<SetClaimDelegate>,
+    claim_delegate: Pubkey,           // Pubkey::default() removes the delegate
+    allow_permissionless_claim: bool, // Let anyone trigger claims
+) -> Result<()> {
+    let user_state = &mut ctx.accounts.user_state;
+    user_state.claim_delegate = claim_delegate;
+    user_state.allow_permissionless_claim = allow_permissionless_claim;
+
+    msg!(
+        "User {} set claim delegate to {} (permissionless: {})",
+        user_state.owner,
+        claim_delegate,
+        allow_permissionless_claim
+    );
+
+    emit!(ClaimDelegateSet {
+        version: EVENT_SCHEMA_VERSION,
+        user: user_state.owner,
+        claim_delegate,
+        allow_permissionless_claim,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    Ok(())
+}
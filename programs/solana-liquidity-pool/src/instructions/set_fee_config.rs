@@ -0,0 +1,52 @@
+use crate::{errors::VaultError, state::*, FeeConfigSet, EVENT_SCHEMA_VERSION};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetFeeConfig<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"pool-state".as_ref()],
+        bump,
+        constraint = pool_state.admin == admin.key() @ VaultError::Unauthorized
+    )]
+    pub pool_state: Account<'info, PoolState>,
+}
+
+pub fn handle_set_fee_config(
+    ctx: Context<SetFeeConfig>,
+    sol_target_weight_bps: u64,
+    base_fee_bps: u64,
+    tax_fee_bps: u64,
+) -> Result<()> {
+    require!(
+        sol_target_weight_bps <= BPS_DENOMINATOR
+            && base_fee_bps <= MAX_COMPOSITION_FEE_BPS
+            && tax_fee_bps <= MAX_COMPOSITION_FEE_BPS,
+        VaultError::InvalidBasisPoints
+    );
+
+    let pool_state = &mut ctx.accounts.pool_state;
+    pool_state.sol_target_weight_bps = sol_target_weight_bps;
+    pool_state.base_fee_bps = base_fee_bps;
+    pool_state.tax_fee_bps = tax_fee_bps;
+
+    msg!(
+        "Fee config set: SOL target {} bps, base fee {} bps, tax fee {} bps.",
+        sol_target_weight_bps,
+        base_fee_bps,
+        tax_fee_bps
+    );
+
+    emit!(FeeConfigSet {
+        version: EVENT_SCHEMA_VERSION,
+        admin: ctx.accounts.admin.key(),
+        sol_target_weight_bps,
+        base_fee_bps,
+        tax_fee_bps,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    Ok(())
+}
@@ -0,0 +1,109 @@
+use crate::{errors::VaultError, state::*, LpMetadataSet, EVENT_SCHEMA_VERSION};
+use anchor_lang::prelude::*;
+use anchor_spl::metadata::{
+    self, mpl_token_metadata::types::DataV2, CreateMetadataAccountsV3, Metadata,
+    UpdateMetadataAccountsV2,
+};
+use anchor_spl::token_interface::Mint;
+
+#[derive(Accounts)]
+pub struct SetLpMetadata<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"pool-state".as_ref()],
+        bump,
+        constraint = pool_state.admin == admin.key() @ VaultError::Unauthorized
+    )]
+    pub pool_state: Account<'info, PoolState>,
+
+    #[account(
+        constraint = lp_token_mint.key() == pool_state.lp_token_mint @ VaultError::InvalidTokenMint
+    )]
+    pub lp_token_mint: InterfaceAccount<'info, Mint>,
+
+    /// CHECK: Metaplex metadata PDA of the LP mint, validated by seeds and by the metadata program
+    #[account(
+        mut,
+        seeds = [b"metadata".as_ref(), token_metadata_program.key().as_ref(), lp_token_mint.key().as_ref()],
+        bump,
+        seeds::program = token_metadata_program.key()
+    )]
+    pub metadata: UncheckedAccount<'info>,
+
+    pub token_metadata_program: Program<'info, Metadata>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+/// Create the LP mint's metadata on first use, then keep it updatable by the pool PDA.
+pub fn handle_set_lp_metadata(
+    ctx: Context<SetLpMetadata>,
+    name: String,
+    symbol: String,
+    uri: String,
+) -> Result<()> {
+    let event = LpMetadataSet {
+        version: EVENT_SCHEMA_VERSION,
+        admin: ctx.accounts.admin.key(),
+        lp_token_mint: ctx.accounts.lp_token_mint.key(),
+        name: name.clone(),
+        symbol: symbol.clone(),
+        uri: uri.clone(),
+        timestamp: Clock::get()?.unix_timestamp,
+    };
+    let data = DataV2 {
+        name,
+        symbol,
+        uri,
+        seller_fee_basis_points: 0,
+        creators: None,
+        collection: None,
+        uses: None,
+    };
+    let signer_seeds: &[&[u8]] = &[b"pool-state".as_ref(), &[ctx.bumps.pool_state]];
+
+    if ctx.accounts.metadata.data_is_empty() {
+        // The pool PDA is both the LP mint authority and the metadata update authority
+        let cpi_ctx = CpiContext::new(
+            ctx.accounts.token_metadata_program.to_account_info(),
+            CreateMetadataAccountsV3 {
+                metadata: ctx.accounts.metadata.to_account_info(),
+                mint: ctx.accounts.lp_token_mint.to_account_info(),
+                mint_authority: ctx.accounts.pool_state.to_account_info(),
+                payer: ctx.accounts.admin.to_account_info(),
+                update_authority: ctx.accounts.pool_state.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+                rent: ctx.accounts.rent.to_account_info(),
+            },
+        );
+        metadata::create_metadata_accounts_v3(
+            cpi_ctx.with_signer(&[signer_seeds]),
+            data,
+            true,
+            true,
+            None,
+        )?;
+        msg!("Created LP token metadata");
+    } else {
+        let cpi_ctx = CpiContext::new(
+            ctx.accounts.token_metadata_program.to_account_info(),
+            UpdateMetadataAccountsV2 {
+                metadata: ctx.accounts.metadata.to_account_info(),
+                update_authority: ctx.accounts.pool_state.to_account_info(),
+            },
+        );
+        metadata::update_metadata_accounts_v2(
+            cpi_ctx.with_signer(&[signer_seeds]),
+            None,
+            Some(data),
+            None,
+            Some(true),
+        )?;
+        msg!("Updated LP token metadata");
+    }
+
+    emit!(event);
+    Ok(())
+}
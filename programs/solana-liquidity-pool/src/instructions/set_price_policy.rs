@@ -0,0 +1,52 @@
+use crate::{errors::VaultError, state::*, PricePolicySet, EVENT_SCHEMA_VERSION};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetPricePolicy<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"pool-state".as_ref()],
+        bump,
+        constraint = pool_state.admin == admin.key() @ VaultError::Unauthorized
+    )]
+    pub pool_state: Account<'info, PoolState>,
+}
+
+pub fn handle_set_price_policy(
+    ctx: Context<SetPricePolicy>,
+    ema_window: u64,
+    price_policy: u8,
+    max_conf_bps: u64,
+) -> Result<()> {
+    require!(
+        ema_window <= MAX_EMA_WINDOW
+            && price_policy <= PRICE_POLICY_SPOT
+            && max_conf_bps <= BPS_DENOMINATOR,
+        VaultError::InvalidPricePolicy
+    );
+
+    let pool_state = &mut ctx.accounts.pool_state;
+    pool_state.ema_window = ema_window;
+    pool_state.price_policy = price_policy;
+    pool_state.max_conf_bps = max_conf_bps;
+
+    msg!(
+        "Price policy set: policy {}, EMA window {} s, max confidence {} bps.",
+        price_policy,
+        ema_window,
+        max_conf_bps
+    );
+
+    emit!(PricePolicySet {
+        version: EVENT_SCHEMA_VERSION,
+        admin: ctx.accounts.admin.key(),
+        ema_window,
+        price_policy,
+        max_conf_bps,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    Ok(())
+}
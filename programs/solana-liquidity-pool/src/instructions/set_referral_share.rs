@@ -0,0 +1,38 @@
+use crate::{errors::VaultError, state::*, ReferralShareSet, EVENT_SCHEMA_VERSION};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetReferralShare<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"pool-state".as_ref()],
+        bump,
+        constraint = pool_state.admin == admin.key() @ VaultError::Unauthorized
+    )]
+    pub pool_state: Account<'info, PoolState>,
+}
+
+pub fn handle_set_referral_share(
+    ctx: Context<SetReferralShare>,
+    referral_share_bps: u64,
+) -> Result<()> {
+    require!(
+        referral_share_bps <= BPS_DENOMINATOR,
+        VaultError::InvalidBasisPoints
+    );
+
+    ctx.accounts.pool_state.referral_share_bps = referral_share_bps;
+
+    msg!("Referral share set to {} bps.", referral_share_bps);
+
+    emit!(ReferralShareSet {
+        version: EVENT_SCHEMA_VERSION,
+        admin: ctx.accounts.admin.key(),
+        referral_share_bps,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    Ok(())
+}
@@ -0,0 +1,52 @@
+use crate::{errors::VaultError, state::*, SwapConfigSet, EVENT_SCHEMA_VERSION};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetSwapConfig<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"pool-state".as_ref()],
+        bump,
+        constraint = pool_state.admin == admin.key() @ VaultError::Unauthorized
+    )]
+    pub pool_state: Account<'info, PoolState>,
+}
+
+pub fn handle_set_swap_config(
+    ctx: Context<SetSwapConfig>,
+    swap_spread_bps: u64,
+    min_sol_weight_bps: u64,
+    max_sol_weight_bps: u64,
+) -> Result<()> {
+    require!(
+        swap_spread_bps <= MAX_SWAP_SPREAD_BPS
+            && min_sol_weight_bps <= max_sol_weight_bps
+            && max_sol_weight_bps <= BPS_DENOMINATOR,
+        VaultError::InvalidBasisPoints
+    );
+
+    let pool_state = &mut ctx.accounts.pool_state;
+    pool_state.swap_spread_bps = swap_spread_bps;
+    pool_state.min_sol_weight_bps = min_sol_weight_bps;
+    pool_state.max_sol_weight_bps = max_sol_weight_bps;
+
+    msg!(
+        "Swap config set: spread {} bps, SOL weight {}-{} bps.",
+        swap_spread_bps,
+        min_sol_weight_bps,
+        max_sol_weight_bps
+    );
+
+    emit!(SwapConfigSet {
+        version: EVENT_SCHEMA_VERSION,
+        admin: ctx.accounts.admin.key(),
+        swap_spread_bps,
+        min_sol_weight_bps,
+        max_sol_weight_bps,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    Ok(())
+}
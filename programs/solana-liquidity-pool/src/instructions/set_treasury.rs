@@ -0,0 +1,42 @@
+use crate::{errors::VaultError, state::*, TreasurySet, EVENT_SCHEMA_VERSION};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+
+#[derive(Accounts)]
+pub struct SetTreasury<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"pool-state".as_ref()],
+        bump,
+        constraint = pool_state.admin == admin.key() @ VaultError::Unauthorized
+    )]
+    pub pool_state: Account<'info, PoolState>,
+
+    #[account(
+        constraint = usdc_reward_vault.key() == pool_state.usdc_reward_vault
+    )]
+    pub usdc_reward_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Treasury USDC account receiving swept rewards
+    #[account(
+        constraint = treasury.mint == usdc_reward_vault.mint @ VaultError::InvalidTokenMint
+    )]
+    pub treasury: InterfaceAccount<'info, TokenAccount>,
+}
+
+pub fn handle_set_treasury(ctx: Context<SetTreasury>) -> Result<()> {
+    ctx.accounts.pool_state.treasury = ctx.accounts.treasury.key();
+
+    msg!("Treasury set to {}", ctx.accounts.treasury.key());
+
+    emit!(TreasurySet {
+        version: EVENT_SCHEMA_VERSION,
+        admin: ctx.accounts.admin.key(),
+        treasury: ctx.accounts.treasury.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    Ok(())
+}
@@ -0,0 +1,46 @@
+use crate::{errors::VaultError, state::*, VestingConfigSet, EVENT_SCHEMA_VERSION};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetVestingConfig<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"pool-state".as_ref()],
+        bump,
+        constraint = pool_state.admin == admin.key() @ VaultError::Unauthorized
+    )]
+    pub pool_state: Account<'info, PoolState>,
+}
+
+pub fn handle_set_vesting_config(
+    ctx: Context<SetVestingConfig>,
+    vesting_period: u64,            // Seconds, 0 disables vesting
+    instant_claim_haircut_bps: u64, // Forfeit on instant claims while vesting is on
+) -> Result<()> {
+    require!(
+        instant_claim_haircut_bps <= BPS_DENOMINATOR,
+        VaultError::InvalidBasisPoints
+    );
+
+    let pool_state = &mut ctx.accounts.pool_state;
+    pool_state.vesting_period = vesting_period;
+    pool_state.instant_claim_haircut_bps = instant_claim_haircut_bps;
+
+    msg!(
+        "Vesting period set to {}s with a {} bps instant claim haircut.",
+        vesting_period,
+        instant_claim_haircut_bps
+    );
+
+    emit!(VestingConfigSet {
+        version: EVENT_SCHEMA_VERSION,
+        admin: ctx.accounts.admin.key(),
+        vesting_period,
+        instant_claim_haircut_bps,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    Ok(())
+}
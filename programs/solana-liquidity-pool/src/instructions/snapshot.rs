@@ -0,0 +1,83 @@
+use crate::{
+    chainlink_program_id, errors::VaultError, instructions::helpers::*, pyth_sol_price_feed,
+    sol_price_feed, state::*,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+#[derive(Accounts)]
+pub struct Snapshot<'info> {
+    #[account(
+        mut,
+        seeds = [b"pool-state".as_ref()],
+        bump
+    )]
+    pub pool_state: Account<'info, PoolState>,
+
+    #[account(
+        mut,
+        seeds = [b"nav-history".as_ref()],
+        bump
+    )]
+    pub nav_history: AccountLoader<'info, NavHistory>,
+
+    #[account(
+        constraint = lp_token_mint.key() == pool_state.lp_token_mint @ VaultError::InvalidTokenMint
+    )]
+    pub lp_token_mint: InterfaceAccount<'info, Mint>,
+
+    /// CHECK: Validated in constraint
+    #[account(address = chainlink_program_id())]
+    pub chainlink_program: AccountInfo<'info>,
+
+    /// CHECK: Validated in constraint
+    #[account(address = sol_price_feed())]
+    pub chainlink_feed: AccountInfo<'info>,
+
+    /// CHECK: Validated in constraint, parsed in `read_pyth_conf_bps`
+    #[account(address = pyth_sol_price_feed())]
+    pub pyth_price_feed: AccountInfo<'info>,
+}
+
+/// Permissionless crank recording the pool's NAV per LP, at most once per
+/// `MIN_SNAPSHOT_INTERVAL`.
+pub fn handle_snapshot(ctx: Context<Snapshot>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let mut nav_history = ctx.accounts.nav_history.load_mut()?;
+    require!(
+        nav_history.count == 0 || now - nav_history.last_snapshot_time >= MIN_SNAPSHOT_INTERVAL,
+        VaultError::SnapshotTooSoon
+    );
+
+    let pool_state = &mut ctx.accounts.pool_state;
+    let price = read_chainlink_price(
+        ctx.accounts.chainlink_program.to_account_info(),
+        ctx.accounts.chainlink_feed.to_account_info(),
+        now,
+    )?;
+    record_sol_price(pool_state, price, now)?;
+    let conf_bps = read_pyth_conf_bps(&ctx.accounts.pyth_price_feed, now)?;
+    record_price_confidence(pool_state, conf_bps)?;
+
+    // Bring the reward index up to date so snapshots compare cleanly
+    accrue_pool_rewards(pool_state, now as u64)?;
+
+    let aum = calculate_aum(pool_state)?;
+    let lp_supply = ctx.accounts.lp_token_mint.supply;
+    let snapshot = NavSnapshot {
+        timestamp: now,
+        aum,
+        lp_supply,
+        nav_per_lp: calculate_nav_per_lp(aum, lp_supply)?,
+        sol_usd_price: i64::try_from(price).map_err(|_| error!(VaultError::MathError))?,
+        reward_index: pool_state.cumulative_reward_per_token.to_le_bytes(),
+    };
+    nav_history.push(snapshot);
+
+    msg!(
+        "Recorded NAV snapshot: {} USD per LP (6 dec), AUM {}.",
+        snapshot.nav_per_lp,
+        aum
+    );
+    Ok(())
+}
@@ -1,6 +1,9 @@
-use crate::{errors::VaultError, state::*};
+use crate::{
+    errors::VaultError, instructions::helpers::begin_reward_period, state::*, RewardsStarted,
+    EVENT_SCHEMA_VERSION,
+};
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
 
 #[derive(Accounts)]
 pub struct StartRewards<'info> {
@@ -17,19 +20,26 @@ pub struct StartRewards<'info> {
 
     /// Admin's USDC token account
     #[account(mut)]
-    pub admin_usdc_account: Account<'info, TokenAccount>,
+    pub admin_usdc_account: InterfaceAccount<'info, TokenAccount>,
 
     /// Program's USDC reward vault
-    #[account(mut)]
-    pub usdc_reward_vault: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        constraint = usdc_reward_vault.key() == pool_state.usdc_reward_vault
+    )]
+    pub usdc_reward_vault: InterfaceAccount<'info, TokenAccount>,
 
-    pub token_program: Program<'info, Token>,
+    /// Mint of the USDC reward vault
+    #[account(constraint = usdc_mint.key() == usdc_reward_vault.mint @ VaultError::InvalidTokenMint)]
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Interface<'info, TokenInterface>,
 }
 
 pub fn handle_start_rewards(
     ctx: Context<StartRewards>,
     usdc_amount: u64,          // Total rewards for the period
-    _tokens_per_interval: u64, // We'll calculate this ourselves
+    _tokens_per_interval: u64, // Derived from the period total instead
 ) -> Result<()> {
     let pool_state = &mut ctx.accounts.pool_state;
     require_keys_eq!(
@@ -39,26 +49,45 @@ pub fn handle_start_rewards(
     );
 
     // Transfer USDC from admin to reward vault
+    let vault_before = ctx.accounts.usdc_reward_vault.amount;
     let cpi_ctx = CpiContext::new(
         ctx.accounts.token_program.to_account_info(),
-        Transfer {
+        TransferChecked {
             from: ctx.accounts.admin_usdc_account.to_account_info(),
+            mint: ctx.accounts.usdc_mint.to_account_info(),
             to: ctx.accounts.usdc_reward_vault.to_account_info(),
             authority: ctx.accounts.admin.to_account_info(),
         },
     );
-    token::transfer(cpi_ctx, usdc_amount)?;
+    token_interface::transfer_checked(cpi_ctx, usdc_amount, ctx.accounts.usdc_mint.decimals)?;
 
-    // Calculate tokens per interval (per second)
-    let tokens_per_interval = usdc_amount
-        .checked_div(604800) // One week in seconds
+    // Fund the period with what arrived, net of any transfer fee
+    ctx.accounts.usdc_reward_vault.reload()?;
+    let received = ctx
+        .accounts
+        .usdc_reward_vault
+        .amount
+        .checked_sub(vault_before)
         .ok_or(VaultError::MathError)?;
 
-    // Update state
-    pool_state.total_rewards_deposited = usdc_amount;
-    pool_state.total_rewards_claimed = 0;
-    pool_state.tokens_per_interval = tokens_per_interval;
-    pool_state.last_distribution_time = Clock::get()?.unix_timestamp as u64;
+    // Roll the new funds (plus anything not yet emitted) into a fresh one-week period
+    let now = Clock::get()?.unix_timestamp as u64;
+    begin_reward_period(pool_state, received, now)?;
+
+    emit!(RewardsStarted {
+        version: EVENT_SCHEMA_VERSION,
+        admin: ctx.accounts.admin.key(),
+        amount: received,
+        period_amount: pool_state.reward_period_amount,
+        start_time: pool_state.reward_start_time,
+        end_time: pool_state.reward_end_time,
+        timestamp: now as i64,
+    });
 
+    msg!(
+        "Started reward period of {} USDC ending at {}.",
+        pool_state.reward_period_amount,
+        pool_state.reward_end_time
+    );
     Ok(())
 }
@@ -0,0 +1,238 @@
+use crate::{
+    chainlink_program_id, errors::VaultError, instructions::helpers::*, pyth_sol_price_feed,
+    sol_price_feed, state::*, Swapped, EVENT_SCHEMA_VERSION,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token::Token;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+
+/// Context for swapping between the SOL and USDC vaults
+#[derive(Accounts)]
+pub struct Swap<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"pool-state".as_ref()],
+        bump
+    )]
+    pub pool_state: Account<'info, PoolState>,
+
+    #[account(
+        mut,
+        constraint = sol_vault.key() == pool_state.sol_vault @ VaultError::InvalidTokenMint
+    )]
+    pub sol_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(constraint = sol_mint.key() == pool_state.sol_mint @ VaultError::InvalidTokenMint)]
+    pub sol_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        constraint = usdc_vault.key() == pool_state.usdc_vault @ VaultError::InvalidTokenMint
+    )]
+    pub usdc_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(constraint = usdc_mint.key() == pool_state.usdc_mint @ VaultError::InvalidTokenMint)]
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+
+    /// User's wrapped SOL account
+    #[account(
+        mut,
+        constraint = user_sol_account.owner == user.key(),
+        constraint = user_sol_account.mint == sol_mint.key()
+    )]
+    pub user_sol_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// User's USDC account
+    #[account(
+        mut,
+        constraint = user_usdc_account.owner == user.key(),
+        constraint = user_usdc_account.mint == usdc_mint.key()
+    )]
+    pub user_usdc_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: Validated in constraint
+    #[account(address = chainlink_program_id())]
+    pub chainlink_program: AccountInfo<'info>,
+
+    /// CHECK: Validated in constraint
+    #[account(address = sol_price_feed())]
+    pub chainlink_feed: AccountInfo<'info>,
+
+    /// CHECK: Validated in constraint, parsed in `read_pyth_conf_bps`
+    #[account(address = pyth_sol_price_feed())]
+    pub pyth_price_feed: AccountInfo<'info>,
+
+    /// Token program of the USDC mint
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// Legacy token program owning the native SOL mint
+    pub sol_token_program: Program<'info, Token>,
+}
+
+/// Swap `amount_in` of SOL for USDC (`sol_to_usdc`) or USDC for SOL at the oracle price
+/// less the pool's spread. The spread stays in the pool and raises NAV for LPs.
+pub fn handle_swap(
+    ctx: Context<Swap>,
+    amount_in: u64,
+    min_amount_out: u64,
+    sol_to_usdc: bool,
+) -> Result<()> {
+    require!(amount_in > 0, VaultError::ZeroAmount);
+    require!(
+        ctx.accounts.pool_state.max_sol_weight_bps > 0,
+        VaultError::SwapsDisabled
+    );
+
+    let now = Clock::get()?.unix_timestamp;
+    let price = read_chainlink_price(
+        ctx.accounts.chainlink_program.to_account_info(),
+        ctx.accounts.chainlink_feed.to_account_info(),
+        now,
+    )?;
+    record_sol_price(&mut ctx.accounts.pool_state, price, now)?;
+    let conf_bps = read_pyth_conf_bps(&ctx.accounts.pyth_price_feed, now)?;
+    record_price_confidence(&mut ctx.accounts.pool_state, conf_bps)?;
+    msg!("Updated SOL/USD price to {} (8 dec)", price);
+    let aum_before = calculate_aum(&ctx.accounts.pool_state)?;
+    let weight_before_bps = sol_weight_bps(&ctx.accounts.pool_state)?;
+
+    // 1) Pull the input into its vault, crediting what actually arrived
+    let (token_program, from, mint, to, decimals) = if sol_to_usdc {
+        (
+            ctx.accounts.sol_token_program.to_account_info(),
+            &ctx.accounts.user_sol_account,
+            &ctx.accounts.sol_mint,
+            &mut ctx.accounts.sol_vault,
+            ctx.accounts.sol_mint.decimals,
+        )
+    } else {
+        (
+            ctx.accounts.token_program.to_account_info(),
+            &ctx.accounts.user_usdc_account,
+            &ctx.accounts.usdc_mint,
+            &mut ctx.accounts.usdc_vault,
+            ctx.accounts.usdc_mint.decimals,
+        )
+    };
+    let vault_before = to.amount;
+    token_interface::transfer_checked(
+        CpiContext::new(
+            token_program,
+            TransferChecked {
+                from: from.to_account_info(),
+                mint: mint.to_account_info(),
+                to: to.to_account_info(),
+                authority: ctx.accounts.user.to_account_info(),
+            },
+        ),
+        amount_in,
+        decimals,
+    )?;
+    to.reload()?;
+    let received = to
+        .amount
+        .checked_sub(vault_before)
+        .ok_or(VaultError::MathError)?;
+
+    // 2) Price the output at the oracle, less the spread. Under the conservative policy
+    //    SOL coming in is valued at the lower of spot and EMA and SOL going out at the higher
+    let pool_state = &mut ctx.accounts.pool_state;
+    let (low_price, high_price) = sol_price_range(pool_state);
+    let value_in_usd = if sol_to_usdc {
+        get_sol_usd_value(received, low_price)?
+    } else {
+        received
+    };
+    let spread_usd = calculate_fee(value_in_usd, pool_state.swap_spread_bps)?;
+    let value_out_usd = value_in_usd - spread_usd;
+    let amount_out = if sol_to_usdc {
+        value_out_usd
+    } else {
+        get_sol_amount_from_usd(value_out_usd, high_price)?
+    };
+    msg!(
+        "Swapping {} in for {} out, spread {} USD (6 dec)",
+        received,
+        amount_out,
+        spread_usd
+    );
+    require!(amount_out >= min_amount_out, VaultError::SlippageExceeded);
+
+    // 3) Book both legs and enforce the composition limits on the result
+    if sol_to_usdc {
+        require!(
+            amount_out <= pool_state.usdc_deposited,
+            VaultError::InsufficientLiquidity
+        );
+        pool_state.sol_deposited = pool_state
+            .sol_deposited
+            .checked_add(received)
+            .ok_or(VaultError::MathError)?;
+        pool_state.usdc_deposited -= amount_out;
+    } else {
+        require!(
+            amount_out <= pool_state.sol_deposited,
+            VaultError::InsufficientLiquidity
+        );
+        pool_state.usdc_deposited = pool_state
+            .usdc_deposited
+            .checked_add(received)
+            .ok_or(VaultError::MathError)?;
+        pool_state.sol_deposited -= amount_out;
+    }
+
+    let aum_after = calculate_aum(pool_state)?;
+    check_composition_limits(pool_state, weight_before_bps)?;
+
+    // 4) Pay the output from the other vault
+    let (token_program, from, mint, to, decimals) = if sol_to_usdc {
+        (
+            ctx.accounts.token_program.to_account_info(),
+            &ctx.accounts.usdc_vault,
+            &ctx.accounts.usdc_mint,
+            &ctx.accounts.user_usdc_account,
+            ctx.accounts.usdc_mint.decimals,
+        )
+    } else {
+        (
+            ctx.accounts.sol_token_program.to_account_info(),
+            &ctx.accounts.sol_vault,
+            &ctx.accounts.sol_mint,
+            &ctx.accounts.user_sol_account,
+            ctx.accounts.sol_mint.decimals,
+        )
+    };
+    let cpi_ctx = CpiContext::new(
+        token_program,
+        TransferChecked {
+            from: from.to_account_info(),
+            mint: mint.to_account_info(),
+            to: to.to_account_info(),
+            authority: pool_state.to_account_info(),
+        },
+    );
+    token_interface::transfer_checked(
+        cpi_ctx.with_signer(&[&[b"pool-state".as_ref(), &[ctx.bumps.pool_state]]]),
+        amount_out,
+        decimals,
+    )?;
+
+    emit!(Swapped {
+        version: EVENT_SCHEMA_VERSION,
+        user: ctx.accounts.user.key(),
+        sol_to_usdc,
+        amount_in: received,
+        amount_out,
+        spread_usd,
+        sol_usd_price: pool_state.sol_usd_price,
+        aum_before,
+        aum_after,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Swap successful.");
+    Ok(())
+}
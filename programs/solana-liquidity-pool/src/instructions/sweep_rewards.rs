@@ -0,0 +1,93 @@
+use crate::state::*;
+use crate::{errors::VaultError, RewardsSwept, EVENT_SCHEMA_VERSION};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+
+#[derive(Accounts)]
+pub struct SweepRewards<'info> {
+    #[account(
+        seeds = [b"pool-state".as_ref()],
+        bump
+    )]
+    pub pool_state: Account<'info, PoolState>,
+
+    #[account(
+        mut,
+        constraint = usdc_reward_vault.key() == pool_state.usdc_reward_vault,
+        constraint = usdc_reward_vault.owner == pool_state.key() @ VaultError::InvalidOwner
+    )]
+    pub usdc_reward_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Mint of the USDC reward vault
+    #[account(constraint = usdc_mint.key() == usdc_reward_vault.mint @ VaultError::InvalidTokenMint)]
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+
+    /// Treasury USDC account configured by the admin
+    #[account(
+        mut,
+        constraint = pool_state.treasury != Pubkey::default() @ VaultError::TreasuryNotSet,
+        constraint = treasury.key() == pool_state.treasury @ VaultError::TreasuryNotSet
+    )]
+    pub treasury: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Permissionlessly return reward vault funds that no user can ever claim to the treasury.
+/// Only the surplus above everything still owed (unclaimed rewards plus vesting) is moved.
+pub fn handle_sweep_rewards(ctx: Context<SweepRewards>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp as u64;
+    let pool_state = &ctx.accounts.pool_state;
+
+    let sweepable_at = pool_state
+        .reward_end_time
+        .checked_add(REWARD_SWEEP_GRACE_PERIOD)
+        .ok_or(VaultError::MathError)?;
+    require!(
+        pool_state.reward_end_time > 0 && now >= sweepable_at,
+        VaultError::SweepGracePeriodActive
+    );
+
+    // Claims are clamped to `deposited - claimed`, so this is the most users can still take
+    let owed = pool_state
+        .total_rewards_deposited
+        .saturating_sub(pool_state.total_rewards_claimed)
+        .checked_add(pool_state.total_rewards_vesting)
+        .ok_or(VaultError::MathError)?;
+    let surplus = ctx.accounts.usdc_reward_vault.amount.saturating_sub(owed);
+    msg!(
+        "Reward vault holds {} USDC, {} owed, {} surplus",
+        ctx.accounts.usdc_reward_vault.amount,
+        owed,
+        surplus
+    );
+    if surplus == 0 {
+        msg!("Nothing to sweep.");
+        return Ok(());
+    }
+
+    let cpi_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        TransferChecked {
+            from: ctx.accounts.usdc_reward_vault.to_account_info(),
+            mint: ctx.accounts.usdc_mint.to_account_info(),
+            to: ctx.accounts.treasury.to_account_info(),
+            authority: pool_state.to_account_info(),
+        },
+    );
+    token_interface::transfer_checked(
+        cpi_ctx.with_signer(&[&[b"pool-state".as_ref(), &[ctx.bumps.pool_state]]]),
+        surplus,
+        ctx.accounts.usdc_mint.decimals,
+    )?;
+
+    emit!(RewardsSwept {
+        version: EVENT_SCHEMA_VERSION,
+        treasury: ctx.accounts.treasury.key(),
+        amount: surplus,
+        timestamp: now as i64,
+    });
+
+    msg!("Swept {} USDC of unowed rewards to the treasury.", surplus);
+    Ok(())
+}
@@ -0,0 +1,153 @@
+use crate::{
+    errors::VaultError, instructions::helpers::*, state::*, LpUnlocked, EVENT_SCHEMA_VERSION,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{
+    self, Burn, CloseAccount, Mint, TokenAccount, TokenInterface, TransferChecked,
+};
+
+#[derive(Accounts)]
+pub struct UnlockLp<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"pool-state".as_ref()],
+        bump
+    )]
+    pub pool_state: Account<'info, PoolState>,
+
+    #[account(
+        mut,
+        seeds = [b"user-state".as_ref(), user.key().as_ref()],
+        bump,
+        constraint = user_state.owner == user.key() @ VaultError::InvalidOwner
+    )]
+    pub user_state: Account<'info, UserState>,
+
+    /// LP token mint
+    #[account(
+        mut,
+        constraint = lp_token_mint.key() == pool_state.lp_token_mint @ VaultError::InvalidTokenMint
+    )]
+    pub lp_token_mint: InterfaceAccount<'info, Mint>,
+
+    /// User's LP token account the unlocked LP is returned to
+    #[account(
+        mut,
+        constraint = user_lp_token_account.owner == user.key(),
+        constraint = user_lp_token_account.mint == lp_token_mint.key()
+    )]
+    pub user_lp_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Escrow holding the locked LP, closed back to the user once emptied
+    #[account(
+        mut,
+        seeds = [b"lp-escrow".as_ref(), user.key().as_ref()],
+        bump,
+        token::mint = lp_token_mint,
+        token::authority = pool_state
+    )]
+    pub lp_escrow: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Release the user's LP lock and return it from escrow. Unlocking before expiry burns a
+/// penalty from the locked LP, which raises the NAV of every remaining LP token.
+pub fn handle_unlock_lp(ctx: Context<UnlockLp>) -> Result<()> {
+    let pool_state = &mut ctx.accounts.pool_state;
+    let user_state = &mut ctx.accounts.user_state;
+
+    // Settle rewards at the boosted weight (an expired lock only pays 1x from its end)
+    update_rewards(pool_state, user_state)?;
+    require!(user_state.locked_lp_amount > 0, VaultError::NoActiveLock);
+
+    // Penalty shrinks linearly as the lock approaches its end
+    let now = Clock::get()?.unix_timestamp as u64;
+    let remaining = user_state.lock_end_time.saturating_sub(now);
+    let duration = user_state
+        .lock_end_time
+        .saturating_sub(user_state.lock_start_time)
+        .max(1);
+    let penalty = (user_state.locked_lp_amount as u128)
+        .checked_mul(MAX_EARLY_UNLOCK_PENALTY_BPS as u128)
+        .ok_or(VaultError::MathError)?
+        .checked_mul(remaining as u128)
+        .ok_or(VaultError::MathError)?
+        .checked_div((BPS_DENOMINATOR as u128) * (duration as u128))
+        .ok_or(VaultError::MathError)? as u64;
+    msg!(
+        "Early unlock of {} LP with {}s remaining, penalty {} LP",
+        user_state.locked_lp_amount,
+        remaining,
+        penalty
+    );
+
+    let signer_seeds: &[&[u8]] = &[b"pool-state".as_ref(), &[ctx.bumps.pool_state]];
+    if penalty > 0 {
+        let cpi_ctx_burn = CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Burn {
+                mint: ctx.accounts.lp_token_mint.to_account_info(),
+                from: ctx.accounts.lp_escrow.to_account_info(),
+                authority: pool_state.to_account_info(),
+            },
+        );
+        token_interface::burn(cpi_ctx_burn.with_signer(&[signer_seeds]), penalty)?;
+
+        user_state.lp_token_balance = user_state
+            .lp_token_balance
+            .checked_sub(penalty)
+            .ok_or(VaultError::MathError)?;
+    }
+
+    let unlocked_amount = user_state.locked_lp_amount - penalty;
+    token_interface::transfer_checked(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.lp_escrow.to_account_info(),
+                mint: ctx.accounts.lp_token_mint.to_account_info(),
+                to: ctx.accounts.user_lp_token_account.to_account_info(),
+                authority: pool_state.to_account_info(),
+            },
+        )
+        .with_signer(&[signer_seeds]),
+        unlocked_amount,
+        ctx.accounts.lp_token_mint.decimals,
+    )?;
+    token_interface::close_account(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            CloseAccount {
+                account: ctx.accounts.lp_escrow.to_account_info(),
+                destination: ctx.accounts.user.to_account_info(),
+                authority: pool_state.to_account_info(),
+            },
+        )
+        .with_signer(&[signer_seeds]),
+    )?;
+
+    user_state.locked_lp_amount = 0;
+    user_state.lock_end_time = now;
+    user_state.lock_multiplier_bps = BPS_DENOMINATOR;
+    update_boosted_balance(pool_state, user_state)?;
+
+    msg!(
+        "LP unlocked. User LP balance: {} (6 dec), boosted balance: {}",
+        user_state.lp_token_balance,
+        user_state.boosted_balance
+    );
+
+    emit!(LpUnlocked {
+        version: EVENT_SCHEMA_VERSION,
+        user: ctx.accounts.user.key(),
+        unlocked_amount,
+        penalty,
+        boosted_balance: user_state.boosted_balance,
+        timestamp: now as i64,
+    });
+    Ok(())
+}
@@ -0,0 +1,39 @@
+use crate::instructions::helpers::update_rewards;
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct ViewPendingRewards<'info> {
+    #[account(
+        seeds = [b"pool-state".as_ref()],
+        bump
+    )]
+    pub pool_state: Account<'info, PoolState>,
+
+    #[account(
+        seeds = [b"user-state".as_ref(), user_state.owner.as_ref()],
+        bump
+    )]
+    pub user_state: Account<'info, UserState>,
+}
+
+/// Run `update_rewards` on copies of the pool and user state and return the result
+/// without writing anything back.
+pub fn handle_view_pending_rewards(ctx: Context<ViewPendingRewards>) -> Result<PendingRewardsView> {
+    let mut pool_state = (*ctx.accounts.pool_state).clone();
+    let mut user_state = (*ctx.accounts.user_state).clone();
+
+    update_rewards(&mut pool_state, &mut user_state)?;
+
+    let available = pool_state
+        .total_rewards_deposited
+        .saturating_sub(pool_state.total_rewards_claimed);
+
+    Ok(PendingRewardsView {
+        pending_rewards: user_state.pending_rewards,
+        claimable_rewards: user_state.pending_rewards.min(available),
+        boosted_balance: user_state.boosted_balance,
+        cumulative_reward_per_token: pool_state.cumulative_reward_per_token,
+        timestamp: Clock::get()?.unix_timestamp,
+    })
+}
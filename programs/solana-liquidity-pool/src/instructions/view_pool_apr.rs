@@ -0,0 +1,73 @@
+use crate::{
+    chainlink_program_id,
+    errors::VaultError,
+    instructions::helpers::{calculate_aum, calculate_nav_per_lp, read_chainlink_price},
+    sol_price_feed,
+    state::*,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+#[derive(Accounts)]
+pub struct ViewPoolApr<'info> {
+    #[account(
+        seeds = [b"pool-state".as_ref()],
+        bump
+    )]
+    pub pool_state: Account<'info, PoolState>,
+
+    /// LP token mint
+    #[account(
+        constraint = lp_token_mint.key() == pool_state.lp_token_mint @ VaultError::InvalidTokenMint
+    )]
+    pub lp_token_mint: InterfaceAccount<'info, Mint>,
+
+    /// CHECK: Validated in constraint
+    #[account(address = chainlink_program_id())]
+    pub chainlink_program: AccountInfo<'info>,
+
+    /// CHECK: Validated in constraint
+    #[account(address = sol_price_feed())]
+    pub chainlink_feed: AccountInfo<'info>,
+}
+
+pub fn handle_view_pool_apr(ctx: Context<ViewPoolApr>) -> Result<PoolAprView> {
+    let now = Clock::get()?.unix_timestamp;
+
+    // Value the pool at the live oracle price without storing it
+    let price = read_chainlink_price(
+        ctx.accounts.chainlink_program.to_account_info(),
+        ctx.accounts.chainlink_feed.to_account_info(),
+        now,
+    )?;
+    let mut pool_state = (*ctx.accounts.pool_state).clone();
+    pool_state.sol_usd_price = price;
+
+    let aum = calculate_aum(&pool_state)?;
+    let lp_supply = ctx.accounts.lp_token_mint.supply;
+    let nav_per_lp = calculate_nav_per_lp(aum, lp_supply)?;
+
+    // Only an active reward period contributes to the APR
+    let reward_rate_per_second = if (now as u64) < pool_state.reward_end_time {
+        pool_state.tokens_per_interval
+    } else {
+        0
+    };
+    let apr_bps = (reward_rate_per_second as u128)
+        .checked_mul(SECONDS_PER_YEAR as u128)
+        .ok_or(VaultError::MathError)?
+        .checked_mul(BPS_DENOMINATOR as u128)
+        .ok_or(VaultError::MathError)?
+        .checked_div(aum.max(1) as u128)
+        .ok_or(VaultError::MathError)? as u64;
+
+    Ok(PoolAprView {
+        sol_usd_price: price,
+        aum,
+        lp_supply,
+        nav_per_lp,
+        reward_rate_per_second,
+        apr_bps,
+        timestamp: now,
+    })
+}
@@ -1,10 +1,11 @@
 use crate::{
-    errors::VaultError, instructions::helpers::*, state::*, CHAINLINK_PROGRAM_ID,
-    DEVNET_SOL_PRICE_FEED, MAINNET_SOL_PRICE_FEED,
+    chainlink_program_id, errors::VaultError, instructions::helpers::*, pyth_sol_price_feed,
+    sol_price_feed, state::*, Withdrawn, EVENT_SCHEMA_VERSION,
 };
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Burn, Mint, Token, TokenAccount, Transfer};
-use chainlink_solana as chainlink;
+use anchor_spl::token_interface::{
+    self, Burn, Mint, TokenAccount, TokenInterface, TransferChecked,
+};
 
 /// Context for withdraw
 #[derive(Accounts)]
@@ -30,9 +31,10 @@ pub struct Withdraw<'info> {
     /// LP token mint
     #[account(
         mut,
-        constraint = lp_token_mint.key() == pool_state.lp_token_mint
+        constraint = lp_token_mint.key() == pool_state.lp_token_mint,
+        mint::token_program = lp_token_program
     )]
-    pub lp_token_mint: Account<'info, Mint>,
+    pub lp_token_mint: InterfaceAccount<'info, Mint>,
 
     /// User's LP token account to burn from
     #[account(
@@ -40,7 +42,7 @@ pub struct Withdraw<'info> {
         constraint = user_lp_token_account.owner == user.key(),
         constraint = user_lp_token_account.mint == lp_token_mint.key()
     )]
-    pub user_lp_token_account: Account<'info, TokenAccount>,
+    pub user_lp_token_account: InterfaceAccount<'info, TokenAccount>,
 
     /// Vault for either SOL (wrapped) or USDC
     #[account(
@@ -49,7 +51,14 @@ pub struct Withdraw<'info> {
             vault_account.key() == pool_state.sol_vault || 
             vault_account.key() == pool_state.usdc_vault
     )]
-    pub vault_account: Account<'info, TokenAccount>,
+    pub vault_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Mint of `vault_account`
+    #[account(
+        constraint = vault_mint.key() == vault_account.mint @ VaultError::InvalidTokenMint,
+        mint::token_program = vault_token_program
+    )]
+    pub vault_mint: InterfaceAccount<'info, Mint>,
 
     /// User's token account to receive withdrawn tokens
     #[account(
@@ -57,23 +66,25 @@ pub struct Withdraw<'info> {
         constraint = user_token_account.owner == user.key(),
         constraint = user_token_account.mint == vault_account.mint
     )]
-    pub user_token_account: Account<'info, TokenAccount>,
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
 
     /// CHECK: Validated in constraint
-    #[account(address = CHAINLINK_PROGRAM_ID.parse::<Pubkey>().unwrap())]
+    #[account(address = chainlink_program_id())]
     pub chainlink_program: AccountInfo<'info>,
 
     /// CHECK: Validated in constraint
-    #[account(
-        address = if cfg!(feature = "devnet") {
-            DEVNET_SOL_PRICE_FEED
-        } else {
-            MAINNET_SOL_PRICE_FEED
-        }.parse::<Pubkey>().unwrap()
-    )]
+    #[account(address = sol_price_feed())]
     pub chainlink_feed: AccountInfo<'info>,
 
-    pub token_program: Program<'info, Token>,
+    /// CHECK: Validated in constraint, parsed in `read_pyth_conf_bps`
+    #[account(address = pyth_sol_price_feed())]
+    pub pyth_price_feed: AccountInfo<'info>,
+
+    /// Token program of the vault's mint
+    pub vault_token_program: Interface<'info, TokenInterface>,
+
+    /// Token program of the LP mint
+    pub lp_token_program: Interface<'info, TokenInterface>,
 }
 
 pub fn handle_withdraw(ctx: Context<Withdraw>, lp_token_amount: u64) -> Result<()> {
@@ -97,19 +108,28 @@ pub fn handle_withdraw(ctx: Context<Withdraw>, lp_token_amount: u64) -> Result<(
 
     // Update any user-level rewards prior to burning LP
     msg!("Updating user rewards before burning LP tokens");
-    update_rewards(pool_state, user_state, &ctx.accounts.lp_token_mint)?;
+    update_rewards(pool_state, user_state)?;
+
+    // Locked LP stays in the pool until the lock expires or is exited early
+    let unlocked_balance = user_state
+        .lp_token_balance
+        .saturating_sub(user_state.locked_lp_amount);
+    if unlocked_balance < lp_token_amount {
+        msg!("Only {} LP tokens are unlocked", unlocked_balance);
+        return err!(VaultError::LpTokensLocked);
+    }
 
     // Burn the LP tokens (6 decimals, matching USD representation)
     msg!("Burning {} LP tokens", lp_token_amount);
     let cpi_ctx_burn = CpiContext::new(
-        ctx.accounts.token_program.to_account_info(),
+        ctx.accounts.lp_token_program.to_account_info(),
         Burn {
             mint: ctx.accounts.lp_token_mint.to_account_info(),
             from: ctx.accounts.user_lp_token_account.to_account_info(),
             authority: ctx.accounts.user.to_account_info(),
         },
     );
-    token::burn(cpi_ctx_burn, lp_token_amount)?;
+    token_interface::burn(cpi_ctx_burn, lp_token_amount)?;
     msg!("LP tokens burned successfully");
 
     // Adjust user's recorded LP balance (6 decimals)
@@ -117,6 +137,7 @@ pub fn handle_withdraw(ctx: Context<Withdraw>, lp_token_amount: u64) -> Result<(
         .lp_token_balance
         .checked_sub(lp_token_amount)
         .ok_or_else(|| error!(VaultError::MathError))?;
+    update_boosted_balance(pool_state, user_state)?;
     msg!(
         "Updated user LP balance to {} (6 dec)",
         user_state.lp_token_balance
@@ -126,24 +147,25 @@ pub fn handle_withdraw(ctx: Context<Withdraw>, lp_token_amount: u64) -> Result<(
     // 1) Compute the pool's total AUM in USD (6 decimals) at this moment.
     // ----------------------------------------------------------------
     msg!("Computing current AUM");
-    // First, if withdrawing SOL, fetch the latest SOL/USD price
-    if ctx.accounts.vault_account.key() == pool_state.sol_vault {
-        msg!("Withdrawing SOL, fetching latest Chainlink price");
-        let round = chainlink::latest_round_data(
-            ctx.accounts.chainlink_program.to_account_info(),
-            ctx.accounts.chainlink_feed.to_account_info(),
-        )?;
-        // Update stored SOL price (8 decimals from Chainlink)
-        pool_state.sol_usd_price = round.answer;
-        msg!("Updated SOL/USD price to {} (8 dec)", round.answer);
-    }
+    // Always refresh the SOL/USD price: the AUM includes the SOL holdings whichever vault pays
+    let now = Clock::get()?.unix_timestamp;
+    let price = read_chainlink_price(
+        ctx.accounts.chainlink_program.to_account_info(),
+        ctx.accounts.chainlink_feed.to_account_info(),
+        now,
+    )?;
+    // Update stored SOL price (8 decimals from Chainlink)
+    record_sol_price(pool_state, price, now)?;
+    let conf_bps = read_pyth_conf_bps(&ctx.accounts.pyth_price_feed, now)?;
+    record_price_confidence(pool_state, conf_bps)?;
+    msg!("Updated SOL/USD price to {} (8 dec)", price);
 
-    // Convert total SOL to USD (returns USD with 6 decimals)
-    let total_sol_usd = get_sol_usd_value(pool_state.sol_deposited, pool_state.sol_usd_price)?;
-    msg!("Total SOL value in USD: {} (6 dec)", total_sol_usd);
-    let current_aum = total_sol_usd
-        .checked_add(pool_state.usdc_deposited)
-        .ok_or_else(|| error!(VaultError::MathError))?;
+    // Under the conservative policy the pool is valued at the lower of spot and EMA,
+    // and outgoing SOL priced at the higher, so a price dip can't be used to drain it
+    let (low_price, high_price) = sol_price_range(pool_state);
+
+    // Convert total SOL to USD (6 decimals) and add total USDC (6 decimals)
+    let current_aum = calculate_aum_at_price(pool_state, low_price)?;
     msg!("Current total AUM: {} (6 dec)", current_aum);
 
     // ----------------------------------------------------------------
@@ -153,13 +175,16 @@ pub fn handle_withdraw(ctx: Context<Withdraw>, lp_token_amount: u64) -> Result<(
     msg!("Calculating withdrawal value");
     let lp_supply = ctx.accounts.lp_token_mint.supply.max(1);
     msg!("Current LP supply: {}", lp_supply);
-    let withdrawal_usd_value = lp_token_amount
-        .checked_mul(current_aum)
-        .ok_or_else(|| error!(VaultError::MathError))?
-        .checked_div(lp_supply)
-        .ok_or_else(|| error!(VaultError::MathError))?;
+    let withdrawal_usd_value = calculate_withdrawal_usd(lp_token_amount, lp_supply, current_aum)?;
     msg!("Withdrawal value in USD: {} (6 dec)", withdrawal_usd_value);
 
+    // The composition fee stays in the vault, accruing to the remaining LPs
+    let is_sol = ctx.accounts.vault_account.key() == pool_state.sol_vault;
+    let fee_bps = calculate_composition_fee_bps(pool_state, is_sol, withdrawal_usd_value, false)?;
+    let fee_usd = calculate_fee(withdrawal_usd_value, fee_bps)?;
+    let payout_usd = withdrawal_usd_value - fee_usd;
+    msg!("Composition fee: {} bps", fee_bps);
+
     // ----------------------------------------------------------------
     // 3) Convert that USD value (6 decimals) into the correct token amount:
     //    - For SOL: Convert to 9 decimals
@@ -169,11 +194,11 @@ pub fn handle_withdraw(ctx: Context<Withdraw>, lp_token_amount: u64) -> Result<(
     let token_amount = if ctx.accounts.vault_account.key() == pool_state.sol_vault {
         msg!("Converting to SOL amount");
         // Convert USD (6 decimals) to SOL (9 decimals)
-        get_sol_amount_from_usd(withdrawal_usd_value, pool_state.sol_usd_price)?
+        get_sol_amount_from_usd(payout_usd, high_price)?
     } else if ctx.accounts.vault_account.key() == pool_state.usdc_vault {
         msg!("Using USDC amount (same as USD value)");
         // USDC uses 6 decimals, same as our USD representation
-        withdrawal_usd_value
+        payout_usd
     } else {
         return err!(VaultError::InvalidTokenMint);
     };
@@ -184,16 +209,18 @@ pub fn handle_withdraw(ctx: Context<Withdraw>, lp_token_amount: u64) -> Result<(
     // ----------------------------------------------------------------
     msg!("Transferring tokens from vault to user");
     let cpi_ctx_transfer = CpiContext::new(
-        ctx.accounts.token_program.to_account_info(),
-        Transfer {
+        ctx.accounts.vault_token_program.to_account_info(),
+        TransferChecked {
             from: ctx.accounts.vault_account.to_account_info(),
+            mint: ctx.accounts.vault_mint.to_account_info(),
             to: ctx.accounts.user_token_account.to_account_info(),
             authority: pool_state.to_account_info(),
         },
     );
-    token::transfer(
+    token_interface::transfer_checked(
         cpi_ctx_transfer.with_signer(&[&[b"pool-state".as_ref(), &[ctx.bumps.pool_state]]]),
         token_amount,
+        ctx.accounts.vault_mint.decimals,
     )?;
     msg!("Token transfer successful");
 
@@ -224,6 +251,21 @@ pub fn handle_withdraw(ctx: Context<Withdraw>, lp_token_amount: u64) -> Result<(
         );
     }
 
+    emit!(Withdrawn {
+        version: EVENT_SCHEMA_VERSION,
+        user: ctx.accounts.user.key(),
+        vault: ctx.accounts.vault_account.key(),
+        amount: token_amount,
+        withdrawal_usd: withdrawal_usd_value,
+        lp_burned: lp_token_amount,
+        nav_per_lp: calculate_nav_per_lp(current_aum, lp_supply)?,
+        fee_usd,
+        sol_usd_price: pool_state.sol_usd_price,
+        aum_before: current_aum,
+        aum_after: calculate_aum(pool_state)?,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
     msg!(
         "Withdrawal successful. Burned {} LP tokens (6 decimals), returned {} {} tokens.",
         lp_token_amount,
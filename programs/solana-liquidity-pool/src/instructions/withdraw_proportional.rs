@@ -0,0 +1,207 @@
+use crate::{
+    errors::VaultError, instructions::helpers::*, state::*, ProportionalWithdrawn,
+    EVENT_SCHEMA_VERSION,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token::Token;
+use anchor_spl::token_interface::{
+    self, Burn, Mint, TokenAccount, TokenInterface, TransferChecked,
+};
+
+/// Context for an in-kind withdrawal across both vaults
+#[derive(Accounts)]
+pub struct WithdrawProportional<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"pool-state".as_ref()],
+        bump
+    )]
+    pub pool_state: Account<'info, PoolState>,
+
+    /// The user's associated UserState
+    #[account(
+        mut,
+        seeds = [b"user-state".as_ref(), user.key().as_ref()],
+        bump,
+        constraint = user_state.owner == user.key()
+    )]
+    pub user_state: Account<'info, UserState>,
+
+    /// LP token mint
+    #[account(
+        mut,
+        constraint = lp_token_mint.key() == pool_state.lp_token_mint
+    )]
+    pub lp_token_mint: InterfaceAccount<'info, Mint>,
+
+    /// User's LP token account to burn from
+    #[account(
+        mut,
+        constraint = user_lp_token_account.owner == user.key(),
+        constraint = user_lp_token_account.mint == lp_token_mint.key()
+    )]
+    pub user_lp_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = sol_vault.key() == pool_state.sol_vault @ VaultError::InvalidTokenMint
+    )]
+    pub sol_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(constraint = sol_mint.key() == pool_state.sol_mint @ VaultError::InvalidTokenMint)]
+    pub sol_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        constraint = usdc_vault.key() == pool_state.usdc_vault @ VaultError::InvalidTokenMint
+    )]
+    pub usdc_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(constraint = usdc_mint.key() == pool_state.usdc_mint @ VaultError::InvalidTokenMint)]
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+
+    /// User's wrapped SOL account receiving the SOL share
+    #[account(
+        mut,
+        constraint = user_sol_account.owner == user.key(),
+        constraint = user_sol_account.mint == sol_mint.key()
+    )]
+    pub user_sol_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// User's USDC account receiving the USDC share
+    #[account(
+        mut,
+        constraint = user_usdc_account.owner == user.key(),
+        constraint = user_usdc_account.mint == usdc_mint.key()
+    )]
+    pub user_usdc_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Token program of the USDC and LP mints
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// Legacy token program owning the native SOL mint
+    pub sol_token_program: Program<'info, Token>,
+}
+
+pub fn handle_withdraw_proportional(
+    ctx: Context<WithdrawProportional>,
+    lp_token_amount: u64,
+) -> Result<()> {
+    require!(lp_token_amount > 0, VaultError::ZeroAmount);
+    msg!(
+        "Starting proportional withdrawal of {} LP tokens (6 dec)",
+        lp_token_amount
+    );
+
+    let pool_state = &mut ctx.accounts.pool_state;
+    let user_state = &mut ctx.accounts.user_state;
+
+    if user_state.lp_token_balance < lp_token_amount {
+        return err!(VaultError::InsufficientLpBalance);
+    }
+
+    update_rewards(pool_state, user_state)?;
+
+    // Locked LP stays in the pool until the lock expires or is exited early
+    let unlocked_balance = user_state
+        .lp_token_balance
+        .saturating_sub(user_state.locked_lp_amount);
+    if unlocked_balance < lp_token_amount {
+        msg!("Only {} LP tokens are unlocked", unlocked_balance);
+        return err!(VaultError::LpTokensLocked);
+    }
+
+    // Size both payouts against the supply before the burn; no price is involved
+    let lp_supply = ctx.accounts.lp_token_mint.supply;
+    let sol_amount =
+        calculate_proportional_share(pool_state.sol_deposited, lp_token_amount, lp_supply)?;
+    let usdc_amount =
+        calculate_proportional_share(pool_state.usdc_deposited, lp_token_amount, lp_supply)?;
+    msg!(
+        "Will withdraw {} SOL (9 dec) and {} USDC (6 dec)",
+        sol_amount,
+        usdc_amount
+    );
+
+    token_interface::burn(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Burn {
+                mint: ctx.accounts.lp_token_mint.to_account_info(),
+                from: ctx.accounts.user_lp_token_account.to_account_info(),
+                authority: ctx.accounts.user.to_account_info(),
+            },
+        ),
+        lp_token_amount,
+    )?;
+
+    user_state.lp_token_balance = user_state
+        .lp_token_balance
+        .checked_sub(lp_token_amount)
+        .ok_or_else(|| error!(VaultError::MathError))?;
+    update_boosted_balance(pool_state, user_state)?;
+
+    let signer_seeds: &[&[u8]] = &[b"pool-state".as_ref(), &[ctx.bumps.pool_state]];
+    if sol_amount > 0 {
+        let cpi_ctx = CpiContext::new(
+            ctx.accounts.sol_token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.sol_vault.to_account_info(),
+                mint: ctx.accounts.sol_mint.to_account_info(),
+                to: ctx.accounts.user_sol_account.to_account_info(),
+                authority: pool_state.to_account_info(),
+            },
+        );
+        token_interface::transfer_checked(
+            cpi_ctx.with_signer(&[signer_seeds]),
+            sol_amount,
+            ctx.accounts.sol_mint.decimals,
+        )?;
+    }
+    if usdc_amount > 0 {
+        let cpi_ctx = CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.usdc_vault.to_account_info(),
+                mint: ctx.accounts.usdc_mint.to_account_info(),
+                to: ctx.accounts.user_usdc_account.to_account_info(),
+                authority: pool_state.to_account_info(),
+            },
+        );
+        token_interface::transfer_checked(
+            cpi_ctx.with_signer(&[signer_seeds]),
+            usdc_amount,
+            ctx.accounts.usdc_mint.decimals,
+        )?;
+    }
+
+    pool_state.sol_deposited = pool_state
+        .sol_deposited
+        .checked_sub(sol_amount)
+        .ok_or_else(|| error!(VaultError::MathError))?;
+    pool_state.usdc_deposited = pool_state
+        .usdc_deposited
+        .checked_sub(usdc_amount)
+        .ok_or_else(|| error!(VaultError::MathError))?;
+
+    emit!(ProportionalWithdrawn {
+        version: EVENT_SCHEMA_VERSION,
+        user: ctx.accounts.user.key(),
+        sol_amount,
+        usdc_amount,
+        lp_burned: lp_token_amount,
+        lp_supply_before: lp_supply,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!(
+        "Proportional withdrawal successful. Burned {} LP tokens, returned {} SOL and {} USDC.",
+        lp_token_amount,
+        sol_amount,
+        usdc_amount
+    );
+    Ok(())
+}
@@ -0,0 +1,212 @@
+use crate::{
+    chainlink_program_id, errors::VaultError, instructions::helpers::*, pyth_sol_price_feed,
+    sol_price_feed, state::*, Withdrawn, EVENT_SCHEMA_VERSION,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{
+    self, Burn, CloseAccount, Mint, TokenAccount, TokenInterface, TransferChecked,
+};
+
+/// Context for withdrawing as native SOL through a temporary wSOL account
+#[derive(Accounts)]
+pub struct WithdrawSol<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"pool-state".as_ref()],
+        bump
+    )]
+    pub pool_state: Account<'info, PoolState>,
+
+    /// The user's associated UserState
+    #[account(
+        mut,
+        seeds = [b"user-state".as_ref(), user.key().as_ref()],
+        bump,
+        constraint = user_state.owner == user.key()
+    )]
+    pub user_state: Account<'info, UserState>,
+
+    /// LP token mint
+    #[account(
+        mut,
+        constraint = lp_token_mint.key() == pool_state.lp_token_mint,
+        mint::token_program = lp_token_program
+    )]
+    pub lp_token_mint: InterfaceAccount<'info, Mint>,
+
+    /// User's LP token account to burn from
+    #[account(
+        mut,
+        constraint = user_lp_token_account.owner == user.key(),
+        constraint = user_lp_token_account.mint == lp_token_mint.key()
+    )]
+    pub user_lp_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// The pool's wrapped SOL vault
+    #[account(
+        mut,
+        constraint = sol_vault.key() == pool_state.sol_vault @ VaultError::InvalidTokenMint,
+        constraint = sol_vault.is_native() @ VaultError::InvalidTokenMint,
+        token::token_program = vault_token_program
+    )]
+    pub sol_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Native mint of the SOL vault
+    #[account(
+        constraint = sol_mint.key() == sol_vault.mint @ VaultError::InvalidTokenMint,
+        mint::token_program = vault_token_program
+    )]
+    pub sol_mint: InterfaceAccount<'info, Mint>,
+
+    /// Temporary wSOL account, closed back to the user within this instruction
+    #[account(
+        init,
+        payer = user,
+        seeds = [b"temp-wsol".as_ref(), user.key().as_ref()],
+        bump,
+        token::mint = sol_mint,
+        token::authority = user,
+        token::token_program = vault_token_program
+    )]
+    pub temp_wsol_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: Validated in constraint
+    #[account(address = chainlink_program_id())]
+    pub chainlink_program: AccountInfo<'info>,
+
+    /// CHECK: Validated in constraint
+    #[account(address = sol_price_feed())]
+    pub chainlink_feed: AccountInfo<'info>,
+
+    /// CHECK: Validated in constraint, parsed in `read_pyth_conf_bps`
+    #[account(address = pyth_sol_price_feed())]
+    pub pyth_price_feed: AccountInfo<'info>,
+
+    /// Token program of the native SOL mint
+    pub vault_token_program: Interface<'info, TokenInterface>,
+
+    /// Token program of the LP mint
+    pub lp_token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_withdraw_sol(ctx: Context<WithdrawSol>, lp_token_amount: u64) -> Result<()> {
+    require!(lp_token_amount > 0, VaultError::ZeroAmount);
+    msg!(
+        "Starting native SOL withdrawal of {} LP tokens (6 dec)",
+        lp_token_amount
+    );
+
+    let pool_state = &mut ctx.accounts.pool_state;
+    let user_state = &mut ctx.accounts.user_state;
+
+    if user_state.lp_token_balance < lp_token_amount {
+        return err!(VaultError::InsufficientLpBalance);
+    }
+
+    update_rewards(pool_state, user_state)?;
+
+    // Locked LP stays in the pool until the lock expires or is exited early
+    let unlocked_balance = user_state
+        .lp_token_balance
+        .saturating_sub(user_state.locked_lp_amount);
+    if unlocked_balance < lp_token_amount {
+        msg!("Only {} LP tokens are unlocked", unlocked_balance);
+        return err!(VaultError::LpTokensLocked);
+    }
+
+    token_interface::burn(
+        CpiContext::new(
+            ctx.accounts.lp_token_program.to_account_info(),
+            Burn {
+                mint: ctx.accounts.lp_token_mint.to_account_info(),
+                from: ctx.accounts.user_lp_token_account.to_account_info(),
+                authority: ctx.accounts.user.to_account_info(),
+            },
+        ),
+        lp_token_amount,
+    )?;
+
+    user_state.lp_token_balance = user_state
+        .lp_token_balance
+        .checked_sub(lp_token_amount)
+        .ok_or_else(|| error!(VaultError::MathError))?;
+    update_boosted_balance(pool_state, user_state)?;
+
+    // Refresh the SOL/USD price before valuing the withdrawal
+    let now = Clock::get()?.unix_timestamp;
+    let price = read_chainlink_price(
+        ctx.accounts.chainlink_program.to_account_info(),
+        ctx.accounts.chainlink_feed.to_account_info(),
+        now,
+    )?;
+    record_sol_price(pool_state, price, now)?;
+    let conf_bps = read_pyth_conf_bps(&ctx.accounts.pyth_price_feed, now)?;
+    record_price_confidence(pool_state, conf_bps)?;
+    msg!("Updated SOL/USD price to {} (8 dec)", price);
+
+    // Value the pool low and the outgoing SOL high under the conservative policy
+    let (low_price, high_price) = sol_price_range(pool_state);
+    let current_aum = calculate_aum_at_price(pool_state, low_price)?;
+    let lp_supply = ctx.accounts.lp_token_mint.supply.max(1);
+    let withdrawal_usd_value = calculate_withdrawal_usd(lp_token_amount, lp_supply, current_aum)?;
+    let fee_bps = calculate_composition_fee_bps(pool_state, true, withdrawal_usd_value, false)?;
+    let fee_usd = calculate_fee(withdrawal_usd_value, fee_bps)?;
+    msg!("Composition fee: {} bps", fee_bps);
+    let lamports = get_sol_amount_from_usd(withdrawal_usd_value - fee_usd, high_price)?;
+    msg!("Will withdraw {} lamports", lamports);
+
+    // Move the wSOL out of the vault, then unwrap it by closing the temporary account
+    let cpi_ctx_transfer = CpiContext::new(
+        ctx.accounts.vault_token_program.to_account_info(),
+        TransferChecked {
+            from: ctx.accounts.sol_vault.to_account_info(),
+            mint: ctx.accounts.sol_mint.to_account_info(),
+            to: ctx.accounts.temp_wsol_account.to_account_info(),
+            authority: pool_state.to_account_info(),
+        },
+    );
+    token_interface::transfer_checked(
+        cpi_ctx_transfer.with_signer(&[&[b"pool-state".as_ref(), &[ctx.bumps.pool_state]]]),
+        lamports,
+        ctx.accounts.sol_mint.decimals,
+    )?;
+    token_interface::close_account(CpiContext::new(
+        ctx.accounts.vault_token_program.to_account_info(),
+        CloseAccount {
+            account: ctx.accounts.temp_wsol_account.to_account_info(),
+            destination: ctx.accounts.user.to_account_info(),
+            authority: ctx.accounts.user.to_account_info(),
+        },
+    ))?;
+
+    pool_state.sol_deposited = pool_state
+        .sol_deposited
+        .checked_sub(lamports)
+        .ok_or_else(|| error!(VaultError::MathError))?;
+
+    emit!(Withdrawn {
+        version: EVENT_SCHEMA_VERSION,
+        user: ctx.accounts.user.key(),
+        vault: ctx.accounts.sol_vault.key(),
+        amount: lamports,
+        withdrawal_usd: withdrawal_usd_value,
+        lp_burned: lp_token_amount,
+        nav_per_lp: calculate_nav_per_lp(current_aum, lp_supply)?,
+        fee_usd,
+        sol_usd_price: pool_state.sol_usd_price,
+        aum_before: current_aum,
+        aum_after: calculate_aum(pool_state)?,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!(
+        "Native SOL withdrawal successful. Burned {} LP tokens, returned {} lamports.",
+        lp_token_amount,
+        lamports
+    );
+    Ok(())
+}
@@ -0,0 +1,95 @@
+use crate::state::*;
+use crate::{errors::VaultError, RewardsClaimed, EVENT_SCHEMA_VERSION};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+
+#[derive(Accounts)]
+pub struct WithdrawVested<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"pool-state".as_ref()],
+        bump
+    )]
+    pub pool_state: Account<'info, PoolState>,
+
+    #[account(
+        mut,
+        seeds = [b"vesting".as_ref(), user.key().as_ref()],
+        bump,
+        constraint = vesting_state.owner == user.key() @ VaultError::InvalidOwner
+    )]
+    pub vesting_state: Account<'info, VestingState>,
+
+    #[account(
+        mut,
+        constraint = usdc_reward_vault.key() == pool_state.usdc_reward_vault,
+        constraint = usdc_reward_vault.owner == pool_state.key() @ VaultError::InvalidOwner
+    )]
+    pub usdc_reward_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Mint of the USDC reward vault
+    #[account(constraint = usdc_mint.key() == usdc_reward_vault.mint @ VaultError::InvalidTokenMint)]
+    pub usdc_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        constraint = user_usdc_account.owner == user.key(),
+        constraint = user_usdc_account.mint == usdc_reward_vault.mint
+    )]
+    pub user_usdc_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn handle_withdraw_vested(ctx: Context<WithdrawVested>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp as u64;
+    let pool_state = &mut ctx.accounts.pool_state;
+    let vesting_state = &mut ctx.accounts.vesting_state;
+
+    let amount = vesting_state.withdrawable_amount(now)?;
+    require!(amount > 0, VaultError::NothingVested);
+    require!(
+        ctx.accounts.usdc_reward_vault.amount >= amount,
+        VaultError::InsufficientRewardBalance
+    );
+
+    // Transfer the vested USDC from the reward vault to the user
+    let cpi_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        TransferChecked {
+            from: ctx.accounts.usdc_reward_vault.to_account_info(),
+            mint: ctx.accounts.usdc_mint.to_account_info(),
+            to: ctx.accounts.user_usdc_account.to_account_info(),
+            authority: pool_state.to_account_info(),
+        },
+    );
+    token_interface::transfer_checked(
+        cpi_ctx.with_signer(&[&[b"pool-state".as_ref(), &[ctx.bumps.pool_state]]]),
+        amount,
+        ctx.accounts.usdc_mint.decimals,
+    )?;
+
+    vesting_state.release(amount)?;
+    pool_state.total_rewards_vesting = pool_state
+        .total_rewards_vesting
+        .checked_sub(amount)
+        .ok_or(VaultError::MathError)?;
+
+    emit!(RewardsClaimed {
+        version: EVENT_SCHEMA_VERSION,
+        user: ctx.accounts.user.key(),
+        amount,
+        timestamp: now as i64,
+        total_claimed: pool_state.total_rewards_claimed,
+    });
+
+    msg!(
+        "User {} withdrew {} vested USDC.",
+        ctx.accounts.user.key(),
+        amount
+    );
+    Ok(())
+}
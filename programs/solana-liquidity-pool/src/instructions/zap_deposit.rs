@@ -0,0 +1,212 @@
+use crate::{
+    chainlink_program_id, errors::VaultError, instructions::helpers::*, pyth_sol_price_feed,
+    sol_price_feed, state::*, Deposited, EVENT_SCHEMA_VERSION,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{
+    self, Mint, MintTo, TokenAccount, TokenInterface, TransferChecked,
+};
+
+/// Context for a one-instruction deposit that mints LP
+#[derive(Accounts)]
+pub struct ZapDeposit<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    /// Global PoolState
+    #[account(
+        mut,
+        seeds = [b"pool-state".as_ref()],
+        bump
+    )]
+    pub pool_state: Account<'info, PoolState>,
+
+    /// The user's token account from which they are depositing
+    #[account(
+        mut,
+        constraint = user_token_account.owner == user.key(),
+        constraint = user_token_account.mint == vault_account.mint
+    )]
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Vault for either SOL (wrapped) or USDC
+    #[account(
+        mut,
+        constraint = vault_account.key() == pool_state.sol_vault
+            || vault_account.key() == pool_state.usdc_vault @ VaultError::InvalidTokenMint
+    )]
+    pub vault_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Mint of `vault_account`
+    #[account(
+        constraint = vault_mint.key() == vault_account.mint @ VaultError::InvalidTokenMint,
+        mint::token_program = vault_token_program
+    )]
+    pub vault_mint: InterfaceAccount<'info, Mint>,
+
+    /// The user's associated UserState
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + UserState::LEN,
+        seeds = [b"user-state".as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub user_state: Account<'info, UserState>,
+
+    /// LP token mint
+    #[account(
+        mut,
+        constraint = lp_token_mint.key() == pool_state.lp_token_mint,
+        mint::token_program = lp_token_program
+    )]
+    pub lp_token_mint: InterfaceAccount<'info, Mint>,
+
+    /// The user's LP token account, created on first deposit
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = lp_token_mint,
+        associated_token::authority = user,
+        associated_token::token_program = lp_token_program
+    )]
+    pub user_lp_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: Validated in constraint
+    #[account(address = chainlink_program_id())]
+    pub chainlink_program: AccountInfo<'info>,
+
+    /// CHECK: Validated in constraint
+    #[account(address = sol_price_feed())]
+    pub chainlink_feed: AccountInfo<'info>,
+
+    /// CHECK: Validated in constraint, parsed in `read_pyth_conf_bps`
+    #[account(address = pyth_sol_price_feed())]
+    pub pyth_price_feed: AccountInfo<'info>,
+
+    /// Token program of the vault's mint
+    pub vault_token_program: Interface<'info, TokenInterface>,
+
+    /// Token program of the LP mint
+    pub lp_token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Deposit `token_amount` and mint LP in one step, returning the LP minted
+pub fn handle_zap_deposit(
+    ctx: Context<ZapDeposit>,
+    token_amount: u64,
+    min_lp_out: u64,
+) -> Result<u64> {
+    require!(token_amount > 0, VaultError::ZeroAmount);
+    msg!("Starting zap deposit of {} tokens", token_amount);
+
+    let pool_state = &mut ctx.accounts.pool_state;
+    let user_state = &mut ctx.accounts.user_state;
+
+    // Both legs are valued at the oracle, so always refresh the price
+    let now = Clock::get()?.unix_timestamp;
+    let price = read_chainlink_price(
+        ctx.accounts.chainlink_program.to_account_info(),
+        ctx.accounts.chainlink_feed.to_account_info(),
+        now,
+    )?;
+    record_sol_price(pool_state, price, now)?;
+    let conf_bps = read_pyth_conf_bps(&ctx.accounts.pyth_price_feed, now)?;
+    record_price_confidence(pool_state, conf_bps)?;
+    msg!("Updated SOL/USD price to {} (8 dec)", price);
+
+    let vault_before = ctx.accounts.vault_account.amount;
+    token_interface::transfer_checked(
+        CpiContext::new(
+            ctx.accounts.vault_token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.user_token_account.to_account_info(),
+                mint: ctx.accounts.vault_mint.to_account_info(),
+                to: ctx.accounts.vault_account.to_account_info(),
+                authority: ctx.accounts.user.to_account_info(),
+            },
+        ),
+        token_amount,
+        ctx.accounts.vault_mint.decimals,
+    )?;
+
+    // Credit only what the vault received, net of any Token-2022 transfer fee
+    ctx.accounts.vault_account.reload()?;
+    let received = ctx
+        .accounts
+        .vault_account
+        .amount
+        .checked_sub(vault_before)
+        .ok_or(VaultError::MathError)?;
+
+    // Value the pool high and the incoming SOL low under the conservative policy
+    let (low_price, high_price) = sol_price_range(pool_state);
+    let initial_aum = calculate_aum_at_price(pool_state, high_price)?;
+    let is_sol = ctx.accounts.vault_account.key() == pool_state.sol_vault;
+    let deposit_usd = if is_sol {
+        get_sol_usd_value(received, low_price)?
+    } else {
+        received
+    };
+
+    let lp_supply = ctx.accounts.lp_token_mint.supply;
+    let (lp_to_mint, fee_usd) =
+        calculate_deposit_lp(pool_state, is_sol, deposit_usd, lp_supply, initial_aum)?;
+
+    if is_sol {
+        pool_state.sol_deposited = pool_state
+            .sol_deposited
+            .checked_add(received)
+            .ok_or(VaultError::MathError)?;
+    } else {
+        pool_state.usdc_deposited = pool_state
+            .usdc_deposited
+            .checked_add(received)
+            .ok_or(VaultError::MathError)?;
+    }
+    require!(lp_to_mint >= min_lp_out, VaultError::SlippageExceeded);
+    msg!("Will mint {} LP tokens (6 dec)", lp_to_mint);
+
+    update_rewards(pool_state, user_state)?;
+
+    let cpi_ctx_mint = CpiContext::new(
+        ctx.accounts.lp_token_program.to_account_info(),
+        MintTo {
+            mint: ctx.accounts.lp_token_mint.to_account_info(),
+            to: ctx.accounts.user_lp_token_account.to_account_info(),
+            authority: pool_state.to_account_info(),
+        },
+    );
+    token_interface::mint_to(
+        cpi_ctx_mint.with_signer(&[&[b"pool-state".as_ref(), &[ctx.bumps.pool_state]]]),
+        lp_to_mint,
+    )?;
+
+    user_state.owner = ctx.accounts.user.key();
+    user_state.lp_token_balance = user_state
+        .lp_token_balance
+        .checked_add(lp_to_mint)
+        .ok_or(VaultError::MathError)?;
+    update_boosted_balance(pool_state, user_state)?;
+
+    emit!(Deposited {
+        version: EVENT_SCHEMA_VERSION,
+        user: ctx.accounts.user.key(),
+        vault: ctx.accounts.vault_account.key(),
+        amount: received,
+        deposit_usd,
+        lp_minted: lp_to_mint,
+        nav_per_lp: calculate_nav_per_lp(initial_aum, lp_supply)?,
+        fee_usd,
+        sol_usd_price: pool_state.sol_usd_price,
+        aum_before: initial_aum,
+        aum_after: calculate_aum(pool_state)?,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Zap deposit successful. Minted {} LP tokens.", lp_to_mint);
+    Ok(lp_to_mint)
+}
@@ -1,13 +1,16 @@
 use anchor_lang::prelude::*;
-use chainlink_solana as chainlink;
 
 // Bring in your other modules
 use instructions::*;
+use state::{PendingRewardsView, PoolAprView};
 
 pub mod errors;
+pub mod events;
 pub mod instructions;
 pub mod state;
 
+pub use events::*;
+
 // Single program ID for this entire program
 declare_id!("3JhuFvHHTxCGeJviVMv4SYUWQ1qAb9tFNy7ZU8dxBhpq");
 
@@ -18,12 +21,54 @@ pub const CHAINLINK_PROGRAM_ID: &str = "HEvSKofvBgfaexv23kMabbYqxasxU3mQ4ibBMEmJ
 pub const MAINNET_SOL_PRICE_FEED: &str = "CH31Xns5z3M1cTAbKW34jcxPPciazARpijcHj9rxtemt";
 pub const DEVNET_SOL_PRICE_FEED: &str = "99B2bTijsU6f1GCT73HmdR7HCFFjGMBcPZY6jZ96ynrR";
 
-#[event]
-pub struct RewardsClaimed {
-    pub user: Pubkey,
-    pub amount: u64,
-    pub timestamp: i64,
-    pub total_claimed: u64,
+// Pyth SOL/USD price accounts, read for their confidence interval
+pub const MAINNET_PYTH_SOL_PRICE_FEED: &str = "H6ARHf6YXhGYeQfUzQNGk6rDNnLBQKrenN712K4AQJEG";
+pub const DEVNET_PYTH_SOL_PRICE_FEED: &str = "J83w4HKfqxwcq3BEMMkPFSppX3gqekLyLJBexebFVkix";
+
+/// Program the SOL/USD price is read through
+#[cfg(not(feature = "localnet"))]
+pub fn chainlink_program_id() -> Pubkey {
+    CHAINLINK_PROGRAM_ID.parse().unwrap()
+}
+
+/// Chainlink SOL/USD feed for the cluster this build targets
+#[cfg(not(feature = "localnet"))]
+pub fn sol_price_feed() -> Pubkey {
+    if cfg!(feature = "devnet") {
+        DEVNET_SOL_PRICE_FEED
+    } else {
+        MAINNET_SOL_PRICE_FEED
+    }
+    .parse()
+    .unwrap()
+}
+
+/// Pyth SOL/USD price account for the cluster this build targets
+#[cfg(not(feature = "localnet"))]
+pub fn pyth_sol_price_feed() -> Pubkey {
+    if cfg!(feature = "devnet") {
+        DEVNET_PYTH_SOL_PRICE_FEED
+    } else {
+        MAINNET_PYTH_SOL_PRICE_FEED
+    }
+    .parse()
+    .unwrap()
+}
+
+// On localnet the mock oracle's single feed stands in for both Chainlink and Pyth
+#[cfg(feature = "localnet")]
+pub fn chainlink_program_id() -> Pubkey {
+    mock_oracle::ID
+}
+
+#[cfg(feature = "localnet")]
+pub fn sol_price_feed() -> Pubkey {
+    mock_oracle::sol_usd_feed_address()
+}
+
+#[cfg(feature = "localnet")]
+pub fn pyth_sol_price_feed() -> Pubkey {
+    mock_oracle::sol_usd_feed_address()
 }
 
 /// The main vault program.
@@ -57,6 +102,43 @@ pub mod solana_liquidity_pool {
         instructions::withdraw::handle_withdraw(ctx, lp_token_amount)
     }
 
+    /// Deposit native SOL, wrapped into the SOL vault in the same instruction
+    pub fn deposit_sol(ctx: Context<DepositSol>, lamports: u64) -> Result<()> {
+        instructions::deposit_sol::handle_deposit_sol(ctx, lamports)
+    }
+
+    /// Deposit SOL or USDC with a minimum LP out and return the LP minted
+    pub fn zap_deposit(
+        ctx: Context<ZapDeposit>,
+        token_amount: u64,
+        min_lp_out: u64,
+    ) -> Result<u64> {
+        instructions::zap_deposit::handle_zap_deposit(ctx, token_amount, min_lp_out)
+    }
+
+    /// Withdraw from the pool as native SOL, unwrapped through a temporary wSOL account
+    pub fn withdraw_sol(ctx: Context<WithdrawSol>, lp_token_amount: u64) -> Result<()> {
+        instructions::withdraw_sol::handle_withdraw_sol(ctx, lp_token_amount)
+    }
+
+    /// Withdraw SOL and USDC in proportion to pool holdings, without an oracle price
+    pub fn withdraw_proportional(
+        ctx: Context<WithdrawProportional>,
+        lp_token_amount: u64,
+    ) -> Result<()> {
+        instructions::withdraw_proportional::handle_withdraw_proportional(ctx, lp_token_amount)
+    }
+
+    /// Swap between SOL and USDC at the oracle price less the pool's spread
+    pub fn swap(
+        ctx: Context<Swap>,
+        amount_in: u64,
+        min_amount_out: u64,
+        sol_to_usdc: bool,
+    ) -> Result<()> {
+        instructions::swap::handle_swap(ctx, amount_in, min_amount_out, sol_to_usdc)
+    }
+
     /// Admin function to withdraw tokens (market making losses)
     pub fn admin_withdraw(ctx: Context<AdminWithdraw>, amount: u64) -> Result<()> {
         instructions::admin_withdraw::handle_admin_withdraw(ctx, amount)
@@ -81,6 +163,148 @@ pub mod solana_liquidity_pool {
         instructions::claim_rewards::handle_claim_rewards(ctx)
     }
 
+    /// Claim user rewards directly into LP tokens
+    pub fn compound_rewards(ctx: Context<CompoundRewards>) -> Result<()> {
+        instructions::compound_rewards::handle_compound_rewards(ctx)
+    }
+
+    /// Settle pending rewards for many user states at once (permissionless)
+    pub fn checkpoint_users<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CheckpointUsers<'info>>,
+    ) -> Result<()> {
+        instructions::checkpoint_users::handle_checkpoint_users(ctx)
+    }
+
+    /// Admin function to create and fund a merkle reward campaign
+    pub fn create_distributor(
+        ctx: Context<CreateDistributor>,
+        campaign_id: u64,
+        merkle_root: [u8; 32],
+        total_amount: u64,
+        num_claims: u64,
+        expiry_time: u64,
+    ) -> Result<()> {
+        instructions::create_distributor::handle_create_distributor(
+            ctx,
+            campaign_id,
+            merkle_root,
+            total_amount,
+            num_claims,
+            expiry_time,
+        )
+    }
+
+    /// Claim from a merkle reward campaign with a proof
+    pub fn claim_merkle_rewards(
+        ctx: Context<ClaimMerkleRewards>,
+        index: u64,
+        amount: u64,
+        proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        instructions::claim_merkle_rewards::handle_claim_merkle_rewards(ctx, index, amount, proof)
+    }
+
+    /// Admin function to reclaim unclaimed campaign funds after expiry
+    pub fn clawback_distributor(ctx: Context<ClawbackDistributor>) -> Result<()> {
+        instructions::clawback_distributor::handle_clawback_distributor(ctx)
+    }
+
+    /// Admin function to set the treasury USDC account
+    pub fn set_treasury(ctx: Context<SetTreasury>) -> Result<()> {
+        instructions::set_treasury::handle_set_treasury(ctx)
+    }
+
+    /// Admin function to create or update the LP token's name, symbol and URI
+    pub fn set_lp_metadata(
+        ctx: Context<SetLpMetadata>,
+        name: String,
+        symbol: String,
+        uri: String,
+    ) -> Result<()> {
+        instructions::set_lp_metadata::handle_set_lp_metadata(ctx, name, symbol, uri)
+    }
+
+    /// Sweep unowed reward surplus to the treasury after the grace period
+    pub fn sweep_rewards(ctx: Context<SweepRewards>) -> Result<()> {
+        instructions::sweep_rewards::handle_sweep_rewards(ctx)
+    }
+
+    /// Read-only: a user's pending rewards as of now (use with simulateTransaction)
+    pub fn view_pending_rewards(ctx: Context<ViewPendingRewards>) -> Result<PendingRewardsView> {
+        instructions::view_pending_rewards::handle_view_pending_rewards(ctx)
+    }
+
+    /// Read-only: pool NAV and annualized reward rate at the oracle price
+    pub fn view_pool_apr(ctx: Context<ViewPoolApr>) -> Result<PoolAprView> {
+        instructions::view_pool_apr::handle_view_pool_apr(ctx)
+    }
+
+    /// Admin function to set the referrer share of referred users' rewards
+    pub fn set_referral_share(
+        ctx: Context<SetReferralShare>,
+        referral_share_bps: u64,
+    ) -> Result<()> {
+        instructions::set_referral_share::handle_set_referral_share(ctx, referral_share_bps)
+    }
+
+    /// Admin function to set the SOL target weight and the composition fee
+    pub fn set_fee_config(
+        ctx: Context<SetFeeConfig>,
+        sol_target_weight_bps: u64,
+        base_fee_bps: u64,
+        tax_fee_bps: u64,
+    ) -> Result<()> {
+        instructions::set_fee_config::handle_set_fee_config(
+            ctx,
+            sol_target_weight_bps,
+            base_fee_bps,
+            tax_fee_bps,
+        )
+    }
+
+    /// Admin function to set the swap spread and the SOL weight limits for swaps
+    pub fn set_swap_config(
+        ctx: Context<SetSwapConfig>,
+        swap_spread_bps: u64,
+        min_sol_weight_bps: u64,
+        max_sol_weight_bps: u64,
+    ) -> Result<()> {
+        instructions::set_swap_config::handle_set_swap_config(
+            ctx,
+            swap_spread_bps,
+            min_sol_weight_bps,
+            max_sol_weight_bps,
+        )
+    }
+
+    /// Admin function to set the EMA window, the mint/burn price policy and the widest
+    /// accepted oracle confidence interval
+    pub fn set_price_policy(
+        ctx: Context<SetPricePolicy>,
+        ema_window: u64,
+        price_policy: u8,
+        max_conf_bps: u64,
+    ) -> Result<()> {
+        instructions::set_price_policy::handle_set_price_policy(
+            ctx,
+            ema_window,
+            price_policy,
+            max_conf_bps,
+        )
+    }
+
+    /// Register a referral code for the signer
+    pub fn register_referral_code(ctx: Context<RegisterReferralCode>, code: [u8; 8]) -> Result<()> {
+        instructions::register_referral_code::handle_register_referral_code(ctx, code)
+    }
+
+    /// Collect referral rewards from referees and claim them
+    pub fn claim_referral_rewards<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ClaimReferralRewards<'info>>,
+    ) -> Result<()> {
+        instructions::claim_referral_rewards::handle_claim_referral_rewards(ctx)
+    }
+
     /// Initialize user state
     pub fn initialize_user(ctx: Context<InitializeUser>) -> Result<()> {
         instructions::initialize_user::handle_initialize_user(ctx)
@@ -89,34 +313,70 @@ pub mod solana_liquidity_pool {
     pub fn force_close_user_state(ctx: Context<ForceCloseUserState>) -> Result<()> {
         instructions::force_close_user_state::handle_force_close_user_state(ctx)
     }
-}
 
-/// A separate module for Chainlink-related instructions (not another `#[program]`).
-/// We import the context structs (e.g., `Initialize`, `UpdateSolUsdPrice`) from `state.rs`.
-pub mod sol_usd_price_feed {
-    use super::*;
-    use crate::state::{Initialize, UpdateSolUsdPrice};
+    /// Lock LP tokens in a pool escrow for 1-52 weeks to earn boosted rewards
+    pub fn lock_lp(ctx: Context<LockLp>, lp_token_amount: u64, lock_weeks: u64) -> Result<()> {
+        instructions::lock_lp::handle_lock_lp(ctx, lp_token_amount, lock_weeks)
+    }
 
-    /// Initialize the PoolState with basic info. This is a separate function from
-    /// the main vault's "initialize" if you only want to run Chainlink-specific code here.
-    pub fn initialize(ctx: Context<Initialize>) -> Result<()> {
-        let pool_state = &mut ctx.accounts.pool_state;
-        pool_state.admin = *ctx.accounts.admin.key;
-        pool_state.sol_vault = *ctx.accounts.sol_vault.key;
-        pool_state.usdc_vault = *ctx.accounts.usdc_vault.key;
-        pool_state.lp_token_mint = *ctx.accounts.lp_token_mint.key;
-        pool_state.usdc_reward_vault = *ctx.accounts.usdc_reward_vault.key;
-        Ok(())
-    }
-
-    /// Pulls the latest SOL/USD price from a Chainlink feed and updates `PoolState`.
-    pub fn update_sol_usd_price(ctx: Context<UpdateSolUsdPrice>) -> Result<()> {
-        let round = chainlink::latest_round_data(
-            ctx.accounts.chainlink_program.to_account_info(),
-            ctx.accounts.chainlink_feed.to_account_info(),
-        )?;
-        let price = round.answer;
-        ctx.accounts.pool_state.sol_usd_price = price;
-        Ok(())
+    /// Return locked LP tokens from escrow (early unlocks pay a penalty)
+    pub fn unlock_lp(ctx: Context<UnlockLp>) -> Result<()> {
+        instructions::unlock_lp::handle_unlock_lp(ctx)
+    }
+
+    /// Admin function to configure reward vesting
+    pub fn set_vesting_config(
+        ctx: Context<SetVestingConfig>,
+        vesting_period: u64,
+        instant_claim_haircut_bps: u64,
+    ) -> Result<()> {
+        instructions::set_vesting_config::handle_set_vesting_config(
+            ctx,
+            vesting_period,
+            instant_claim_haircut_bps,
+        )
+    }
+
+    /// Claim rewards on behalf of a user, paid to the user's USDC account
+    pub fn claim_rewards_for(ctx: Context<ClaimRewardsFor>) -> Result<()> {
+        instructions::claim_rewards_for::handle_claim_rewards_for(ctx)
+    }
+
+    /// Set who may claim rewards on the user's behalf
+    pub fn set_claim_delegate(
+        ctx: Context<SetClaimDelegate>,
+        claim_delegate: Pubkey,
+        allow_permissionless_claim: bool,
+    ) -> Result<()> {
+        instructions::set_claim_delegate::handle_set_claim_delegate(
+            ctx,
+            claim_delegate,
+            allow_permissionless_claim,
+        )
+    }
+
+    /// Claim user rewards into a linear vesting schedule
+    pub fn claim_rewards_vested(ctx: Context<ClaimRewardsVested>) -> Result<()> {
+        instructions::claim_rewards_vested::handle_claim_rewards_vested(ctx)
+    }
+
+    /// Withdraw rewards that have vested
+    pub fn withdraw_vested(ctx: Context<WithdrawVested>) -> Result<()> {
+        instructions::withdraw_vested::handle_withdraw_vested(ctx)
+    }
+
+    /// Admin function to create the NAV snapshot history account
+    pub fn initialize_nav_history(ctx: Context<InitializeNavHistory>) -> Result<()> {
+        instructions::initialize_nav_history::handle_initialize_nav_history(ctx)
+    }
+
+    /// Record a NAV per LP snapshot (permissionless, rate limited)
+    pub fn snapshot(ctx: Context<Snapshot>) -> Result<()> {
+        instructions::snapshot::handle_snapshot(ctx)
+    }
+
+    /// Refresh the stored SOL/USD price, EMA and confidence interval (permissionless)
+    pub fn refresh_price(ctx: Context<RefreshPrice>) -> Result<()> {
+        instructions::refresh_price::handle_refresh_price(ctx)
     }
 }
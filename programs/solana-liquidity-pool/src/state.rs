@@ -1,50 +1,13 @@
+use crate::errors::VaultError;
 use anchor_lang::prelude::*;
 
-// -----------------------------------------------
-// Context structs for Chainlink usage
-// -----------------------------------------------
-
-#[derive(Accounts)]
-pub struct Initialize<'info> {
-    #[account(init, payer = admin, space = 8 + PoolState::LEN)]
-    pub pool_state: Account<'info, PoolState>,
-
-    #[account(mut)]
-    pub admin: Signer<'info>,
-
-    /// CHECK: This is not dangerous if you're verifying usage in your logic
-    pub sol_vault: AccountInfo<'info>,
-
-    /// CHECK: Same as above, ensure usage is validated
-    pub usdc_vault: AccountInfo<'info>,
-
-    /// CHECK: Similarly ensure it's your mint
-    pub lp_token_mint: AccountInfo<'info>,
-
-    /// CHECK: Program's reward vault
-    pub usdc_reward_vault: AccountInfo<'info>,
-
-    pub system_program: Program<'info, System>,
-}
-
-#[derive(Accounts)]
-pub struct UpdateSolUsdPrice<'info> {
-    #[account(mut)]
-    pub pool_state: Account<'info, PoolState>,
-
-    /// CHECK: This is the Chainlink program's address
-    pub chainlink_program: AccountInfo<'info>,
-
-    /// CHECK: This is the Chainlink feed account
-    pub chainlink_feed: AccountInfo<'info>,
-}
-
 // -----------------------------------------------
 // Data structures for the pool
 // -----------------------------------------------
 
 /// PoolState holds global info about the liquidity pool.
 #[account]
+#[derive(Default)]
 pub struct PoolState {
     /// Admin authority who can withdraw funds and set rewards
     pub admin: Pubkey,
@@ -82,15 +45,89 @@ pub struct PoolState {
     // -----------------------------------------------
     // New fields to ensure we never exceed the deposited rewards
     // -----------------------------------------------
-    /// How many USDC tokens the admin deposited for this reward period (6 decimals)
+    /// How many USDC tokens the admin deposited across all reward periods (6 decimals)
     pub total_rewards_deposited: u64,
 
-    /// How many USDC have actually been claimed by users so far (6 decimals)
+    /// How many USDC have actually been claimed by users across all reward periods (6 decimals)
     pub total_rewards_claimed: u64,
 
     pub cumulative_reward_per_token: u128, // Using u128 for precision
 
     pub last_distribution_time: u64,
+
+    /// Sum of every user's boosted LP balance, used as the reward denominator (6 decimals)
+    pub total_boosted_supply: u64,
+
+    /// Length of the linear vesting schedule for vested claims, in seconds (0 = vesting disabled)
+    pub vesting_period: u64,
+
+    /// Share of an instant claim forfeited to other stakers while vesting is enabled
+    pub instant_claim_haircut_bps: u64,
+
+    /// Rewards moved into vesting schedules but not yet withdrawn (6 decimals)
+    pub total_rewards_vesting: u64,
+
+    /// USDC token account that receives swept reward surplus
+    pub treasury: Pubkey,
+
+    /// USDC emitted linearly between `reward_start_time` and `reward_end_time` (6 decimals)
+    pub reward_period_amount: u64,
+
+    /// Part of `reward_period_amount` already emitted into the reward index (6 decimals)
+    pub reward_period_emitted: u64,
+
+    /// Division remainder of the reward index, carried into the next accrual (scaled by PRECISION)
+    pub reward_index_remainder: u128,
+
+    /// Share of a referred user's rewards accrued to their referrer
+    pub referral_share_bps: u64,
+
+    /// Native SOL mint held by `sol_vault`
+    pub sol_mint: Pubkey,
+
+    /// USDC mint held by `usdc_vault` and `usdc_reward_vault`
+    pub usdc_mint: Pubkey,
+
+    /// Target share of AUM held as SOL; USDC targets the remainder
+    pub sol_target_weight_bps: u64,
+
+    /// Flat fee on deposits and withdrawals that change the pool composition
+    pub base_fee_bps: u64,
+
+    /// Extra fee for moving the pool away from target, or rebate for moving it closer
+    pub tax_fee_bps: u64,
+
+    /// Spread charged on swaps against the oracle price, kept by the pool
+    pub swap_spread_bps: u64,
+
+    /// Lowest SOL share of AUM a swap may leave behind, unless it moved the pool closer to it
+    pub min_sol_weight_bps: u64,
+
+    /// Highest SOL share of AUM a swap may leave behind, unless it moved the pool closer to it
+    /// (0 = swaps disabled)
+    pub max_sol_weight_bps: u64,
+
+    /// Exponential moving average of the SOL/USD price (8 decimals)
+    pub sol_usd_ema: i128,
+
+    /// Timestamp the EMA was last updated
+    pub ema_last_update: i64,
+
+    /// Time constant of the EMA in seconds: a round this long after the last one carries
+    /// half the weight (0 = follow spot)
+    pub ema_window: u64,
+
+    /// How spot and EMA combine when pricing mints and burns, see `PRICE_POLICY_*`
+    pub price_policy: u8,
+
+    /// Latest Pyth SOL/USD confidence interval as a share of the price
+    pub sol_usd_conf_bps: u64,
+
+    /// Widest confidence interval accepted for mints and burns (0 = no limit)
+    pub max_conf_bps: u64,
+
+    /// Rewards with no staker to receive them, added to the next reward period (6 decimals)
+    pub reward_carryover: u64,
 }
 
 impl PoolState {
@@ -109,11 +146,82 @@ impl PoolState {
         + 8                   // total_rewards_deposited
         + 8                   // total_rewards_claimed
         + 16                  // cumulative_reward_per_token
-        + 8; // last_distribution_time
+        + 8                   // last_distribution_time
+        + 8                   // total_boosted_supply
+        + 8                   // vesting_period
+        + 8                   // instant_claim_haircut_bps
+        + 8                   // total_rewards_vesting
+        + 32                  // treasury
+        + 8                   // reward_period_amount
+        + 8                   // reward_period_emitted
+        + 16                  // reward_index_remainder
+        + 8                   // referral_share_bps
+        + 32                  // sol_mint
+        + 32                  // usdc_mint
+        + 8                   // sol_target_weight_bps
+        + 8                   // base_fee_bps
+        + 8                   // tax_fee_bps
+        + 8                   // swap_spread_bps
+        + 8                   // min_sol_weight_bps
+        + 8                   // max_sol_weight_bps
+        + 16                  // sol_usd_ema
+        + 8                   // ema_last_update
+        + 8                   // ema_window
+        + 1                   // price_policy
+        + 8                   // sol_usd_conf_bps
+        + 8                   // max_conf_bps
+        + 8; // reward_carryover
 }
 
+/// Time after `reward_end_time` before unowed reward surplus can be swept (30 days)
+pub const REWARD_SWEEP_GRACE_PERIOD: u64 = 2_592_000;
+
+/// Basis point denominator used for multipliers and penalties
+pub const BPS_DENOMINATOR: u64 = 10_000;
+
+/// One week in seconds, the length of a reward period and the unit for LP lock durations
+pub const WEEK_SECONDS: u64 = 604_800;
+
+/// Shortest allowed LP lock, in weeks
+pub const MIN_LOCK_WEEKS: u64 = 1;
+
+/// Longest allowed LP lock, in weeks
+pub const MAX_LOCK_WEEKS: u64 = 52;
+
+/// Reward multiplier for a maximum-length lock (2.5x)
+pub const MAX_LOCK_BOOST_BPS: u64 = 25_000;
+
+/// Upper bound for the base and tax components of the composition fee (5%)
+pub const MAX_COMPOSITION_FEE_BPS: u64 = 500;
+
+/// Price mints at max(spot, ema) and burns at min(spot, ema)
+pub const PRICE_POLICY_CONSERVATIVE: u8 = 0;
+
+/// Price mints and burns at the spot price only
+pub const PRICE_POLICY_SPOT: u8 = 1;
+
+/// Longest allowed EMA horizon (1 day)
+pub const MAX_EMA_WINDOW: u64 = 86_400;
+
+/// EMA horizon a new pool starts with (30 minutes)
+pub const DEFAULT_EMA_WINDOW: u64 = 1_800;
+
+/// Widest confidence interval a new pool accepts (2%)
+pub const DEFAULT_MAX_CONF_BPS: u64 = 200;
+
+/// Oldest oracle update accepted when pricing, in seconds
+pub const MAX_ORACLE_AGE: i64 = 60;
+
+/// Upper bound for the swap spread (5%)
+pub const MAX_SWAP_SPREAD_BPS: u64 = 500;
+
+/// Share of the locked LP burned when unlocking right after locking.
+/// Scales down linearly with the time remaining on the lock.
+pub const MAX_EARLY_UNLOCK_PENALTY_BPS: u64 = 5_000;
+
 /// UserState stores user-specific info (in practice often combined into a single PDA).
 #[account]
+#[derive(Default)]
 pub struct UserState {
     /// User pubkey
     pub owner: Pubkey,
@@ -129,6 +237,36 @@ pub struct UserState {
 
     /// Previous cumulative reward per token
     pub previous_cumulated_reward_per_token: u128,
+
+    /// Portion of `lp_token_balance` that is time-locked and cannot be withdrawn
+    pub locked_lp_amount: u64,
+
+    /// Timestamp when the current lock was created or last extended
+    pub lock_start_time: u64,
+
+    /// Timestamp when the current lock expires
+    pub lock_end_time: u64,
+
+    /// Reward multiplier applied to the locked LP (10_000 = 1x)
+    pub lock_multiplier_bps: u64,
+
+    /// Reward-weighted LP balance: unlocked LP plus locked LP times the multiplier
+    pub boosted_balance: u64,
+
+    /// Account allowed to trigger claims on the owner's behalf (default = none)
+    pub claim_delegate: Pubkey,
+
+    /// Whether anyone may trigger claims on the owner's behalf
+    pub allow_permissionless_claim: bool,
+
+    /// ReferrerState of the referrer registered on the first deposit (default = none)
+    pub referrer: Pubkey,
+
+    /// Referral share of this user's rewards not yet collected by the referrer (6 decimals)
+    pub referral_rewards_owed: u64,
+
+    /// Timestamp rewards were last settled into `pending_rewards`
+    pub last_reward_update: u64,
 }
 
 impl UserState {
@@ -136,7 +274,317 @@ impl UserState {
         + 8  // lp_token_balance
         + 8  // last_claim_timestamp
         + 8  // pending_rewards
-        + 16; // previous_cumulated_reward_per_token
+        + 16 // previous_cumulated_reward_per_token
+        + 8  // locked_lp_amount
+        + 8  // lock_start_time
+        + 8  // lock_end_time
+        + 8  // lock_multiplier_bps
+        + 8  // boosted_balance
+        + 32 // claim_delegate
+        + 1  // allow_permissionless_claim
+        + 32 // referrer
+        + 8  // referral_rewards_owed
+        + 8; // last_reward_update
+}
+
+/// ReferrerState is a referral code owned by a partner, accruing their share of referees' rewards.
+#[account]
+pub struct ReferrerState {
+    /// Referrer pubkey, who claims the referral rewards
+    pub owner: Pubkey,
+
+    /// Referral code (part of the PDA seeds)
+    pub code: [u8; 8],
+
+    /// Number of users who registered this referrer
+    pub referee_count: u64,
+
+    /// Referral rewards collected from referees and not yet claimed (6 decimals)
+    pub pending_rewards: u64,
+
+    /// Referral rewards claimed over the referrer's lifetime (6 decimals)
+    pub total_claimed: u64,
+}
+
+impl ReferrerState {
+    pub const LEN: usize = 32 // owner
+        + 8  // code
+        + 8  // referee_count
+        + 8  // pending_rewards
+        + 8; // total_claimed
+}
+
+/// VestingState holds a user's claimed rewards that release linearly over `PoolState::vesting_period`.
+#[account]
+#[derive(Default)]
+pub struct VestingState {
+    /// User pubkey
+    pub owner: Pubkey,
+
+    /// USDC vesting on the current schedule (6 decimals)
+    pub total_amount: u64,
+
+    /// Portion of `total_amount` already withdrawn (6 decimals)
+    pub released_amount: u64,
+
+    /// Vested USDC carried over from earlier schedules, withdrawable now (6 decimals)
+    pub carried_amount: u64,
+
+    /// Timestamp when the current schedule started
+    pub start_time: u64,
+
+    /// Timestamp when the current schedule is fully vested
+    pub end_time: u64,
+}
+
+impl VestingState {
+    pub const LEN: usize = 32 // owner
+        + 8  // total_amount
+        + 8  // released_amount
+        + 8  // carried_amount
+        + 8  // start_time
+        + 8; // end_time
+
+    /// Amount of the current schedule vested at `now`
+    pub fn vested_amount(&self, now: u64) -> Result<u64> {
+        if now >= self.end_time {
+            return Ok(self.total_amount);
+        }
+
+        let elapsed = now.saturating_sub(self.start_time);
+        let duration = self.end_time.saturating_sub(self.start_time).max(1);
+        let vested = (self.total_amount as u128)
+            .checked_mul(elapsed as u128)
+            .ok_or(VaultError::MathError)?
+            .checked_div(duration as u128)
+            .ok_or(VaultError::MathError)?;
+
+        Ok(vested as u64)
+    }
+
+    /// Amount that can be withdrawn at `now`
+    pub fn withdrawable_amount(&self, now: u64) -> Result<u64> {
+        self.vested_amount(now)?
+            .checked_sub(self.released_amount)
+            .and_then(|unreleased| unreleased.checked_add(self.carried_amount))
+            .ok_or_else(|| error!(VaultError::MathError))
+    }
+
+    /// Add `amount` to the schedule. Anything already vested is carried over and the
+    /// unvested remainder plus `amount` vests again from `now` over `vesting_period`.
+    pub fn extend(&mut self, amount: u64, now: u64, vesting_period: u64) -> Result<()> {
+        let vested = self.vested_amount(now)?;
+
+        self.carried_amount = self.withdrawable_amount(now)?;
+        self.total_amount = self
+            .total_amount
+            .checked_sub(vested)
+            .and_then(|unvested| unvested.checked_add(amount))
+            .ok_or(VaultError::MathError)?;
+        self.released_amount = 0;
+        self.start_time = now;
+        self.end_time = now
+            .checked_add(vesting_period)
+            .ok_or(VaultError::MathError)?;
+
+        Ok(())
+    }
+
+    /// Mark `amount` as withdrawn, taking it from carried-over rewards first
+    pub fn release(&mut self, amount: u64) -> Result<()> {
+        let from_carry = amount.min(self.carried_amount);
+        self.carried_amount -= from_carry;
+        self.released_amount = self
+            .released_amount
+            .checked_add(amount - from_carry)
+            .ok_or(VaultError::MathError)?;
+
+        Ok(())
+    }
+}
+
+/// MerkleDistributor is a funded off-chain-computed reward campaign claimable with merkle proofs.
+#[account]
+pub struct MerkleDistributor {
+    /// Admin-chosen campaign identifier (part of the PDA seeds)
+    pub campaign_id: u64,
+
+    /// Root of the merkle tree of `(index, claimant, amount)` leaves
+    pub merkle_root: [u8; 32],
+
+    /// Mint of the token being distributed
+    pub mint: Pubkey,
+
+    /// Token account holding the campaign funds, owned by this distributor
+    pub vault: Pubkey,
+
+    /// Amount funded into the vault at creation
+    pub total_amount: u64,
+
+    /// Amount claimed so far
+    pub total_claimed: u64,
+
+    /// Number of leaves in the tree (claim indexes are `0..num_claims`)
+    pub num_claims: u64,
+
+    /// Timestamp after which claims stop and the admin can claw back leftovers
+    pub expiry_time: u64,
+
+    /// Whether the leftover funds have been clawed back
+    pub clawed_back: bool,
+
+    /// PDA bump, used to sign for the vault
+    pub bump: u8,
+}
+
+impl MerkleDistributor {
+    pub const LEN: usize = 8 // campaign_id
+        + 32 // merkle_root
+        + 32 // mint
+        + 32 // vault
+        + 8  // total_amount
+        + 8  // total_claimed
+        + 8  // num_claims
+        + 8  // expiry_time
+        + 1  // clawed_back
+        + 1; // bump
+}
+
+/// Largest campaign whose claim bitmap still fits in a single account created by the program
+pub const MAX_MERKLE_CLAIMS: u64 = 80_000;
+
+/// ClaimBitmap records which leaf indexes of a distributor have been claimed, one bit each.
+#[account]
+pub struct ClaimBitmap {
+    /// Distributor this bitmap belongs to
+    pub distributor: Pubkey,
+
+    /// Claimed flags, bit `index % 8` of byte `index / 8`
+    pub claimed: Vec<u8>,
+}
+
+impl ClaimBitmap {
+    /// Space needed for a bitmap covering `num_claims` leaves
+    pub fn space(num_claims: u64) -> usize {
+        32 // distributor
+            + 4 // vec length prefix
+            + num_claims.div_ceil(8) as usize
+    }
+
+    pub fn is_claimed(&self, index: u64) -> bool {
+        self.claimed[(index / 8) as usize] & (1 << (index % 8)) != 0
+    }
+
+    pub fn set_claimed(&mut self, index: u64) {
+        self.claimed[(index / 8) as usize] |= 1 << (index % 8);
+    }
+}
+
+// -----------------------------------------------
+// Read-only views returned via return data
+// -----------------------------------------------
+
+/// Returned by `view_pending_rewards`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct PendingRewardsView {
+    /// Rewards accrued to the user as of now (6 decimals)
+    pub pending_rewards: u64,
+
+    /// Part of `pending_rewards` the reward pool can currently pay (6 decimals)
+    pub claimable_rewards: u64,
+
+    /// User's reward-weighted LP balance
+    pub boosted_balance: u64,
+
+    /// Pool reward index the values were computed at
+    pub cumulative_reward_per_token: u128,
+
+    pub timestamp: i64,
+}
+
+/// Returned by `view_pool_apr`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct PoolAprView {
+    /// SOL/USD price read from the oracle (8 decimals)
+    pub sol_usd_price: i128,
+
+    /// Pool AUM at that price (6 decimals)
+    pub aum: u64,
+
+    /// LP token supply (6 decimals)
+    pub lp_supply: u64,
+
+    /// USD value of one LP token (6 decimals)
+    pub nav_per_lp: u64,
+
+    /// USDC emitted per second while rewards are active (6 decimals)
+    pub reward_rate_per_second: u64,
+
+    /// Annualized rewards as a share of AUM, in basis points
+    pub apr_bps: u64,
+
+    pub timestamp: i64,
+}
+
+/// Seconds in a 365-day year
+pub const SECONDS_PER_YEAR: u64 = 31_536_000;
+
+/// Number of NAV snapshots kept before the oldest is overwritten
+pub const NAV_HISTORY_CAPACITY: usize = 128;
+
+/// Minimum time between two NAV snapshots (1 hour)
+pub const MIN_SNAPSHOT_INTERVAL: i64 = 3_600;
+
+/// One point of LP share-price history
+#[zero_copy]
+#[derive(Default)]
+pub struct NavSnapshot {
+    pub timestamp: i64,
+
+    /// Pool AUM in USD (6 decimals)
+    pub aum: u64,
+
+    /// LP supply (6 decimals)
+    pub lp_supply: u64,
+
+    /// AUM per LP token in USD (6 decimals)
+    pub nav_per_lp: u64,
+
+    /// SOL/USD price used for `aum` (8 decimals)
+    pub sol_usd_price: i64,
+
+    /// `cumulative_reward_per_token` as little-endian bytes, kept as bytes for zero-copy alignment
+    pub reward_index: [u8; 16],
+}
+
+/// Ring buffer of NAV snapshots, written by the permissionless `snapshot` crank.
+#[account(zero_copy)]
+pub struct NavHistory {
+    /// Slot the next snapshot is written to
+    pub head: u64,
+
+    /// Number of snapshots written, saturating at `NAV_HISTORY_CAPACITY`
+    pub count: u64,
+
+    /// Timestamp of the latest snapshot
+    pub last_snapshot_time: i64,
+
+    pub snapshots: [NavSnapshot; NAV_HISTORY_CAPACITY],
+}
+
+impl NavHistory {
+    pub const LEN: usize = 8 // head
+        + 8 // count
+        + 8 // last_snapshot_time
+        + 56 * NAV_HISTORY_CAPACITY; // snapshots
+
+    /// Write `snapshot` over the oldest slot
+    pub fn push(&mut self, snapshot: NavSnapshot) {
+        self.snapshots[self.head as usize] = snapshot;
+        self.head = (self.head + 1) % NAV_HISTORY_CAPACITY as u64;
+        self.count = (self.count + 1).min(NAV_HISTORY_CAPACITY as u64);
+        self.last_snapshot_time = snapshot.timestamp;
+    }
 }
 
 // -----------------------------------------------
@@ -148,6 +596,7 @@ impl UserState {
 /// Input:
 ///   - sol_amount: Amount of SOL with 9 decimals (1 SOL = 1_000_000_000)
 ///   - sol_usd_price: Chainlink price with 8 decimals
+///
 /// Output:
 ///   - USD value with 6 decimals (1 USD = 1_000_000)
 pub fn get_sol_usd_value(sol_amount: u64, sol_usd_price: i128) -> Result<u64> {
@@ -178,6 +627,7 @@ pub fn get_sol_usd_value(sol_amount: u64, sol_usd_price: i128) -> Result<u64> {
 /// Input:
 ///   - usd_value: USD amount with 6 decimals (1 USD = 1_000_000)
 ///   - sol_usd_price: Chainlink price with 8 decimals
+///
 /// Output:
 ///   - SOL amount with 9 decimals (1 SOL = 1_000_000_000)
 pub fn get_sol_amount_from_usd(usd_value: u64, sol_usd_price: i128) -> Result<u64> {
@@ -198,3 +648,42 @@ pub fn get_sol_amount_from_usd(usd_value: u64, sol_usd_price: i128) -> Result<u6
     msg!("Conversion result: {} SOL (9 dec)", sol);
     Ok(sol as u64)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn vesting_extend_carries_vested_and_restarts_unvested() {
+        let mut vesting = VestingState::default();
+        vesting.extend(1_000, 100, 1_000).unwrap();
+        assert_eq!(vesting.withdrawable_amount(600).unwrap(), 500);
+
+        // Half vested: 500 is carried, the other 500 vests again with the new 1_000
+        vesting.extend(1_000, 600, 1_000).unwrap();
+        assert_eq!(vesting.carried_amount, 500);
+        assert_eq!(vesting.total_amount, 1_500);
+        assert_eq!(vesting.withdrawable_amount(600).unwrap(), 500);
+        assert_eq!(vesting.withdrawable_amount(1_100).unwrap(), 1_250);
+        assert_eq!(vesting.withdrawable_amount(1_600).unwrap(), 2_000);
+    }
+
+    #[test]
+    fn vesting_release_takes_carried_amount_first() {
+        let mut vesting = VestingState::default();
+        vesting.extend(1_000, 0, 1_000).unwrap();
+        vesting.extend(1_000, 500, 1_000).unwrap();
+
+        vesting.release(300).unwrap();
+        assert_eq!(vesting.carried_amount, 200);
+        assert_eq!(vesting.released_amount, 0);
+
+        // At 1_000 half of the 1_500 has vested on top of the 200 still carried
+        assert_eq!(vesting.withdrawable_amount(1_000).unwrap(), 950);
+        vesting.release(950).unwrap();
+        assert_eq!(vesting.carried_amount, 0);
+        assert_eq!(vesting.released_amount, 750);
+        assert_eq!(vesting.withdrawable_amount(1_000).unwrap(), 0);
+        assert_eq!(vesting.withdrawable_amount(1_500).unwrap(), 750);
+    }
+}
@@ -0,0 +1,17 @@
+#!/usr/bin/env bash
+# Local validator with the Pyth program and price accounts cloned from mainnet-beta, for
+# running a regular (non-`localnet`) build against real oracle data. Needs network access.
+set -euo pipefail
+
+CLONES=(
+  FsJ3A3u2vn5cTVofAjvy6y5kwABJAqYWpe4975bi2epH # Pyth program
+  H6ARHf6YXhGYeQfUzQNGk6rDNnLBQKrenN712K4AQJEG # SOL/USD price feed
+  Gnt27xtC473ZT2Mw5u8wZ68Z3gULkSTb5DuxJy7eJotD # USDC/USD price feed
+)
+
+args=(--reset --url https://api.mainnet-beta.solana.com)
+for address in "${CLONES[@]}"; do
+  args+=(--clone "$address")
+done
+
+exec solana-test-validator "${args[@]}" "$@"
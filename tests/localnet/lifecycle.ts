@@ -0,0 +1,211 @@
+import * as anchor from "@coral-xyz/anchor";
+import { AnchorError, Program } from "@coral-xyz/anchor";
+import { SolanaLiquidityPool } from "../../target/types/solana_liquidity_pool";
+import { MockOracle } from "../../target/types/mock_oracle";
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import {
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  NATIVE_MINT,
+  TOKEN_PROGRAM_ID,
+  createMint,
+  getAccount,
+  getAssociatedTokenAddressSync,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+} from "@solana/spl-token";
+import { expect } from "chai";
+
+/**
+ * Full deposit/withdraw lifecycle against the mock oracle.
+ * Requires a `localnet` build: anchor build -- --features localnet
+ * Run with: anchor run test-localnet --provider.cluster localnet
+ */
+describe("localnet lifecycle", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace
+    .SolanaLiquidityPool as Program<SolanaLiquidityPool>;
+  const oracle = anchor.workspace.MockOracle as Program<MockOracle>;
+  const payer = (provider.wallet as anchor.Wallet).payer;
+  const user = provider.wallet.publicKey;
+
+  // 8 decimals, like Chainlink
+  const SOL_PRICE = new anchor.BN(150_00000000);
+  const SOL_CONF = new anchor.BN(10_000000);
+  const DEPOSIT_USDC = 1_000_000_000; // 1,000 USDC
+
+  const [feed] = PublicKey.findProgramAddressSync(
+    [Buffer.from("sol-usd")],
+    oracle.programId
+  );
+  const [poolState] = PublicKey.findProgramAddressSync(
+    [Buffer.from("pool-state")],
+    program.programId
+  );
+  const [userState] = PublicKey.findProgramAddressSync(
+    [Buffer.from("user-state"), user.toBuffer()],
+    program.programId
+  );
+  const lpTokenMint = Keypair.generate();
+
+  let usdcMint: PublicKey;
+  let usdcVault: PublicKey;
+  let userUsdcAccount: PublicKey;
+  let userLpTokenAccount: PublicKey;
+
+  const oracleAccounts = () => ({
+    chainlinkProgram: oracle.programId,
+    chainlinkFeed: feed,
+    pythPriceFeed: feed,
+  });
+
+  const withdraw = (lpAmount: number) =>
+    program.methods
+      .withdraw(new anchor.BN(lpAmount))
+      .accountsStrict({
+        user,
+        poolState,
+        userState,
+        lpTokenMint: lpTokenMint.publicKey,
+        userLpTokenAccount,
+        vaultAccount: usdcVault,
+        vaultMint: usdcMint,
+        userTokenAccount: userUsdcAccount,
+        ...oracleAccounts(),
+        vaultTokenProgram: TOKEN_PROGRAM_ID,
+        lpTokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+
+  it("initializes the mock feed", async () => {
+    // Anyone may create the feed first and become its authority
+    await oracle.methods
+      .initializeFeed(SOL_PRICE, SOL_CONF)
+      .accountsStrict({
+        authority: user,
+        feed,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    const priceFeed = await oracle.account.priceFeed.fetch(feed);
+    expect(priceFeed.authority.toBase58()).to.equal(user.toBase58());
+    expect(priceFeed.price.toString()).to.equal(SOL_PRICE.toString());
+  });
+
+  it("initializes the pool", async () => {
+    usdcMint = await createMint(provider.connection, payer, user, null, 6);
+    userUsdcAccount = (
+      await getOrCreateAssociatedTokenAccount(
+        provider.connection,
+        payer,
+        usdcMint,
+        user
+      )
+    ).address;
+    await mintTo(
+      provider.connection,
+      payer,
+      usdcMint,
+      userUsdcAccount,
+      payer,
+      DEPOSIT_USDC
+    );
+
+    const vaultPda = (seed: string, mint: PublicKey) =>
+      PublicKey.findProgramAddressSync(
+        [Buffer.from(seed), poolState.toBuffer(), mint.toBuffer()],
+        program.programId
+      )[0];
+    usdcVault = vaultPda("vault", usdcMint);
+
+    await program.methods
+      .initialize()
+      .accountsStrict({
+        admin: user,
+        poolState,
+        solMint: NATIVE_MINT,
+        usdcMint,
+        solVault: vaultPda("vault", NATIVE_MINT),
+        usdcVault,
+        usdcRewardVault: vaultPda("reward-vault", usdcMint),
+        lpTokenMint: lpTokenMint.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        solTokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+      })
+      .signers([lpTokenMint])
+      .rpc();
+
+    const pool = await program.account.poolState.fetch(poolState);
+    expect(pool.emaWindow.toNumber()).to.be.greaterThan(0);
+    expect(pool.maxConfBps.toNumber()).to.be.greaterThan(0);
+  });
+
+  it("deposits USDC for LP", async () => {
+    userLpTokenAccount = getAssociatedTokenAddressSync(
+      lpTokenMint.publicKey,
+      user
+    );
+
+    await program.methods
+      .deposit(new anchor.BN(DEPOSIT_USDC))
+      .accountsStrict({
+        user,
+        poolState,
+        userTokenAccount: userUsdcAccount,
+        vaultAccount: usdcVault,
+        vaultMint: usdcMint,
+        userState,
+        lpTokenMint: lpTokenMint.publicKey,
+        userLpTokenAccount,
+        ...oracleAccounts(),
+        referrerState: null,
+        vaultTokenProgram: TOKEN_PROGRAM_ID,
+        lpTokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    // The first deposit mints LP 1:1 with USD
+    const lp = await getAccount(provider.connection, userLpTokenAccount);
+    expect(Number(lp.amount)).to.equal(DEPOSIT_USDC);
+  });
+
+  it("withdraws after a price move", async () => {
+    await oracle.methods
+      .setPrice(new anchor.BN(120_00000000), SOL_CONF, null)
+      .accountsStrict({ authority: user, feed })
+      .rpc();
+
+    const lpAmount = DEPOSIT_USDC / 2;
+    await withdraw(lpAmount);
+
+    // A USDC-only pool is unaffected by the SOL price
+    const usdc = await getAccount(provider.connection, userUsdcAccount);
+    expect(Number(usdc.amount)).to.equal(lpAmount);
+    const pool = await program.account.poolState.fetch(poolState);
+    expect(pool.solUsdPrice.toString()).to.equal("12000000000");
+  });
+
+  it("rejects withdrawals against a stale feed", async () => {
+    const stale = Math.floor(Date.now() / 1000) - 600;
+    await oracle.methods
+      .setPrice(SOL_PRICE, SOL_CONF, new anchor.BN(stale))
+      .accountsStrict({ authority: user, feed })
+      .rpc();
+
+    try {
+      await withdraw(1_000_000);
+      expect.fail("withdrawal against a stale feed succeeded");
+    } catch (error) {
+      expect(error).to.be.instanceOf(AnchorError);
+      expect((error as AnchorError).error.errorCode.code).to.equal(
+        "StalePrice"
+      );
+    }
+  });
+});